@@ -0,0 +1,286 @@
+//! Derive macros for the parsing traits in `advent_of_code::helpers::parsing`.
+//!
+//! All derives read a `#[aoc(pattern = "...")]` attribute where `{field}` placeholders
+//! mark the struct fields and everything else is matched literally. Use `{{` and `}}`
+//! for literal braces.
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Field, Fields, Lit, Meta, NestedMeta,
+    Type,
+};
+
+const TEXT_PARSER_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+];
+
+/// Derives `AocLineParsable`, so the pattern is followed by a line ending or the end of input.
+#[proc_macro_derive(AocLineParsable, attributes(aoc))]
+pub fn derive_aoc_line_parsable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, "AocLineParsable", "parse_from_line")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `AocParsable`, so the pattern alone describes the whole record.
+#[proc_macro_derive(AocParsable, attributes(aoc))]
+pub fn derive_aoc_parsable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, "AocParsable", "parse_from_string")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `AocBlockParsable`, so the pattern describes one blank-line separated block.
+#[proc_macro_derive(AocBlockParsable, attributes(aoc))]
+pub fn derive_aoc_block_parsable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, "AocBlockParsable", "parse_from_block")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `AocFormattable`, writing the fields back out in the shape of the pattern.
+#[proc_macro_derive(AocFormattable, attributes(aoc))]
+pub fn derive_aoc_formattable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_formattable(&input, "AocFormattable", "write_to")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `AocLineFormattable`, so the pattern is written followed by a line ending.
+#[proc_macro_derive(AocLineFormattable, attributes(aoc))]
+pub fn derive_aoc_line_formattable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_formattable(&input, "AocLineFormattable", "write_line")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+fn parse_pattern(pattern: &str, span: Span) -> syn::Result<Vec<Segment>> {
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let name: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                if name.is_empty() {
+                    return Err(syn::Error::new(span, "empty `{}` placeholder in pattern"));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(name.trim().to_string()));
+            }
+            '}' => return Err(syn::Error::new(span, "unmatched `}` in pattern, use `}}`")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+fn read_pattern(input: &DeriveInput) -> syn::Result<(String, Span)> {
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("aoc")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            return match list.nested.first() {
+                Some(NestedMeta::Meta(Meta::NameValue(name_value)))
+                    if name_value.path.is_ident("pattern") =>
+                {
+                    match &name_value.lit {
+                        Lit::Str(pattern) => Ok((pattern.value(), pattern.span())),
+                        lit => Err(syn::Error::new_spanned(lit, "pattern must be a string")),
+                    }
+                }
+                _ => Err(syn::Error::new_spanned(
+                    attr,
+                    "expected `#[aoc(pattern = \"...\")]`",
+                )),
+            };
+        }
+    }
+    Err(syn::Error::new(
+        Span::call_site(),
+        "missing `#[aoc(pattern = \"...\")]` attribute",
+    ))
+}
+
+/// Integers go through the matching `text_*` parser, anything else has to be `AocParsable`.
+fn field_parser(ty: &Type) -> TokenStream2 {
+    if let Type::Path(type_path) = ty {
+        if let Some(ident) = type_path.path.get_ident() {
+            if TEXT_PARSER_TYPES.contains(&ident.to_string().as_str()) {
+                let parser = format_ident!("text_{}", ident);
+                return quote!(::advent_of_code::helpers::parsing::#parser());
+            }
+        }
+    }
+    quote!(<#ty as ::advent_of_code::helpers::parsing::AocParsable>::parse_from_string)
+}
+
+enum Step<'a> {
+    Literal(String),
+    Field(&'a Field),
+}
+
+/// Matches the pattern up with the struct fields, checking that every field appears exactly once.
+fn pattern_steps(input: &DeriveInput) -> syn::Result<Vec<Step<'_>>> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "only structs with named fields can be derived from a pattern",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "only structs can be derived from a pattern",
+            ))
+        }
+    };
+
+    let (pattern, pattern_span) = read_pattern(input)?;
+    let segments = parse_pattern(&pattern, pattern_span)?;
+
+    let mut steps = vec![];
+    let mut used: Vec<&Ident> = vec![];
+    for segment in segments.into_iter() {
+        match segment {
+            Segment::Literal(literal) => steps.push(Step::Literal(literal)),
+            Segment::Field(name) => {
+                let field = fields
+                    .iter()
+                    .find(|field| field.ident.as_ref().is_some_and(|ident| ident == &name))
+                    .ok_or_else(|| {
+                        syn::Error::new(pattern_span, format!("no field named `{}`", name))
+                    })?;
+                let ident = field.ident.as_ref().unwrap();
+                if used.contains(&ident) {
+                    return Err(syn::Error::new(
+                        pattern_span,
+                        format!("field `{}` appears more than once in pattern", name),
+                    ));
+                }
+                used.push(ident);
+                steps.push(Step::Field(field));
+            }
+        }
+    }
+    if let Some(missing) = fields
+        .iter()
+        .find(|field| !used.contains(&field.ident.as_ref().unwrap()))
+    {
+        return Err(syn::Error::new_spanned(
+            missing,
+            "every field must appear in the pattern",
+        ));
+    }
+    Ok(steps)
+}
+
+fn expand(input: &DeriveInput, trait_name: &str, method_name: &str) -> syn::Result<TokenStream2> {
+    let mut steps = vec![];
+    let mut used = vec![];
+    for step in pattern_steps(input)? {
+        match step {
+            Step::Literal(literal) => steps.push(quote! {
+                let (__input, _) = ::nom::Parser::parse(
+                    &mut ::nom::bytes::complete::tag(#literal),
+                    __input,
+                )?;
+            }),
+            Step::Field(field) => {
+                let ident = field.ident.as_ref().unwrap();
+                used.push(ident);
+                let parser = field_parser(&field.ty);
+                steps.push(quote! {
+                    let (__input, #ident) = ::nom::Parser::parse(&mut #parser, __input)?;
+                });
+            }
+        }
+    }
+
+    let name = &input.ident;
+    let context = name.to_string();
+    let trait_ident = format_ident!("{}", trait_name);
+    let method_ident = format_ident!("{}", method_name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::advent_of_code::helpers::parsing::#trait_ident
+            for #name #ty_generics #where_clause
+        {
+            fn #method_ident<'__input>(
+                input: &'__input [u8],
+            ) -> ::advent_of_code::helpers::parsing::ParsingResult<'__input, Self> {
+                ::nom::error::context(
+                    #context,
+                    |__input: &'__input [u8]|
+                        -> ::advent_of_code::helpers::parsing::ParsingResult<'__input, Self> {
+                        #(#steps)*
+                        Ok((__input, Self { #(#used),* }))
+                    },
+                )(input)
+            }
+        }
+    })
+}
+
+fn expand_formattable(
+    input: &DeriveInput,
+    trait_name: &str,
+    method_name: &str,
+) -> syn::Result<TokenStream2> {
+    let steps: Vec<TokenStream2> = pattern_steps(input)?
+        .into_iter()
+        .map(|step| match step {
+            Step::Literal(literal) => quote! {
+                ::std::fmt::Write::write_str(out, #literal)?;
+            },
+            Step::Field(field) => {
+                let ident = field.ident.as_ref().unwrap();
+                quote! {
+                    ::advent_of_code::helpers::parsing::AocFormattable::write_to(&self.#ident, out)?;
+                }
+            }
+        })
+        .collect();
+
+    let name = &input.ident;
+    let trait_ident = format_ident!("{}", trait_name);
+    let method_ident = format_ident!("{}", method_name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::advent_of_code::helpers::parsing::#trait_ident
+            for #name #ty_generics #where_clause
+        {
+            fn #method_ident(&self, out: &mut impl ::std::fmt::Write) -> ::std::fmt::Result {
+                #(#steps)*
+                Ok(())
+            }
+        }
+    })
+}
@@ -0,0 +1,59 @@
+use advent_of_code::helpers::parsing::{
+    extract_all_ints, fast_unsigned, parse_complete, split_lines, text_u32,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use nom::{character::complete::line_ending, multi::many0, sequence::terminated, Parser};
+
+/// One million pseudo-random numbers of varying length, one per line.
+fn numeric_input() -> Vec<u8> {
+    let mut state: u32 = 0x2545_f491;
+    let mut input = Vec::new();
+    for _ in 0..1_000_000 {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        let value = state >> (state % 32);
+        input.extend_from_slice(value.to_string().as_bytes());
+        input.push(b'\n');
+    }
+    input
+}
+
+fn bench_numbers(c: &mut Criterion) {
+    let input = numeric_input();
+    let mut group = c.benchmark_group("parse one u32 per line");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+
+    group.bench_function("text_u32", |b| {
+        b.iter(|| {
+            let numbers: Vec<u32> =
+                parse_complete(&input, many0(terminated(text_u32(), line_ending))).unwrap();
+            black_box(numbers)
+        })
+    });
+    group.bench_function("fast_unsigned", |b| {
+        b.iter(|| {
+            let numbers: Vec<u32> = parse_complete(
+                &input,
+                many0(terminated(fast_unsigned::<u32>(), line_ending)),
+            )
+            .unwrap();
+            black_box(numbers)
+        })
+    });
+    group.bench_function("split_lines + fast_unsigned", |b| {
+        b.iter(|| {
+            let numbers: Vec<u32> = split_lines(&input)
+                .map(|line| fast_unsigned::<u32>().parse(line).unwrap().1)
+                .collect();
+            black_box(numbers)
+        })
+    });
+    group.bench_function("extract_all_ints", |b| {
+        b.iter(|| black_box(extract_all_ints(&input)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_numbers);
+criterion_main!(benches);
@@ -1,17 +1,16 @@
-use advent_of_code::helpers::parsing::{line_ending_or_eof, parse_all, text_u32, AocParsable};
-use nom::{character::complete::line_ending, multi::many1, sequence::terminated};
+use advent_of_code::helpers::parsing::{
+    parse_all_blocks, text_u32, AocBlockParsable, ParsingError,
+};
+use nom::{character::complete::line_ending, multi::separated_list1};
 
 #[derive(Debug)]
 struct ElfBaggage {
     items: Vec<u32>,
 }
 
-impl AocParsable for ElfBaggage {
-    fn parse_from_string<'a>(
-        input: &'a [u8],
-    ) -> Result<(&'a [u8], Self), nom::Err<nom::error::Error<&'a [u8]>>> {
-        let parse_line = terminated(text_u32(), line_ending);
-        let (rest, items) = terminated(many1(parse_line), line_ending_or_eof())(input)?;
+impl AocBlockParsable for ElfBaggage {
+    fn parse_from_block<'a>(input: &'a [u8]) -> Result<(&'a [u8], Self), ParsingError<'a>> {
+        let (rest, items) = separated_list1(line_ending, text_u32())(input)?;
         return Ok((rest, ElfBaggage { items }));
     }
 }
@@ -23,12 +22,12 @@ fn elf_totals(elf_baggages: Vec<ElfBaggage>) -> impl Iterator<Item = u32> {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let elf_baggages: Vec<ElfBaggage> = parse_all(input.as_bytes()).unwrap();
+    let elf_baggages: Vec<ElfBaggage> = parse_all_blocks(input.as_bytes()).unwrap();
     Some(elf_totals(elf_baggages).max().unwrap())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let elf_baggages: Vec<ElfBaggage> = parse_all(input.as_bytes()).unwrap();
+    let elf_baggages: Vec<ElfBaggage> = parse_all_blocks(input.as_bytes()).unwrap();
     let top_3: [u32; 3] = elf_totals(elf_baggages).fold([0u32; 3], |mut acc, elf_total| {
         if elf_total > acc[0] {
             acc.rotate_right(1);
@@ -1,5 +1,5 @@
 use advent_of_code::helpers::parsing::{
-    generic_error_for_input, iterate_all, line_ending_or_eof, AocParsable,
+    generic_error_for_input, iterate_all, line_ending_or_eof, AocParsable, ParsingError,
 };
 use nom::{
     character::complete::{one_of, space1},
@@ -79,7 +79,7 @@ impl PartOneRound {
 impl AocParsable for PartOneRound {
     fn parse_from_string<'a>(
         input: &'a [u8],
-    ) -> Result<(&'a [u8], Self), nom::Err<nom::error::Error<&'a [u8]>>> {
+    ) -> Result<(&'a [u8], Self), ParsingError<'a>> {
         let mut parser = terminated(
             separated_pair(one_of("ABC"), space1::<&'a [u8], ()>, one_of("XYZ")),
             line_ending_or_eof(),
@@ -100,6 +100,7 @@ impl AocParsable for PartOneRound {
 
 pub fn part_one(input: &str) -> Option<u32> {
     iterate_all::<PartOneRound>(input.as_bytes())
+        .map(Result::unwrap)
         .map(|round| round.my_score() as u32)
         .reduce(|a, b| a + b)
 }
@@ -120,7 +121,7 @@ impl PartTwoRound {
 impl AocParsable for PartTwoRound {
     fn parse_from_string<'a>(
         input: &'a [u8],
-    ) -> Result<(&'a [u8], Self), nom::Err<nom::error::Error<&'a [u8]>>> {
+    ) -> Result<(&'a [u8], Self), ParsingError<'a>> {
         let mut parser = terminated(
             separated_pair(one_of("ABC"), space1::<&'a [u8], ()>, one_of("XYZ")),
             line_ending_or_eof(),
@@ -141,6 +142,7 @@ impl AocParsable for PartTwoRound {
 
 pub fn part_two(input: &str) -> Option<u32> {
     iterate_all(input.as_bytes())
+        .map(Result::unwrap)
         .map(|round: PartTwoRound| round.my_score() as u32)
         .reduce(|a, b| a + b)
 }
@@ -1,7 +1,6 @@
 use advent_of_code::helpers::parsing::{
     generic_error_for_input, iterate_all, AocLineParsable, AocParsable, ParsingError,
 };
-use nom::sequence::tuple;
 
 #[derive(Debug)]
 struct RucksackBitSet(u64);
@@ -32,7 +31,7 @@ fn parse_rucksack(input: &[u8]) -> Result<(&[u8], RucksackBitSet), ParsingError>
 
     let mut rucksack_bit_set = RucksackBitSet::new();
     let mut i = 0;
-    while input[i] != b'\n' {
+    while i < input.len() && input[i] != b'\n' {
         let c = input[i];
         if c >= b'a' && c <= b'z' {
             rucksack_bit_set.add(c - b'a' + 1);
@@ -44,7 +43,7 @@ fn parse_rucksack(input: &[u8]) -> Result<(&[u8], RucksackBitSet), ParsingError>
         i += 1;
     }
 
-    return Ok((&input[i + 1..], rucksack_bit_set));
+    return Ok((&input[i..], rucksack_bit_set));
 }
 
 #[derive(Debug)]
@@ -63,19 +62,16 @@ struct ElfGroup([ElfPocket; 3]);
 impl AocParsable for ElfGroup {
     fn parse_from_string(
         input: &[u8],
-    ) -> Result<(&[u8], Self), nom::Err<nom::error::Error<&[u8]>>> {
-        let (rest, (elf1, elf2, elf3)) = tuple((
-            ElfPocket::parse_from_line,
-            ElfPocket::parse_from_line,
-            ElfPocket::parse_from_line,
-        ))(input)?;
-        Ok((rest, ElfGroup([elf1, elf2, elf3])))
+    ) -> Result<(&[u8], Self), ParsingError> {
+        let (rest, pockets) = <[ElfPocket; 3]>::parse_from_string(input)?;
+        Ok((rest, ElfGroup(pockets)))
     }
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
     Some(
         iterate_all(input.as_bytes())
+            .map(Result::unwrap)
             .map(|elf_group: ElfGroup| {
                 let [mut elf_group_1, elf_group_2, elf_group_3] = elf_group.0;
                 elf_group_1.0.intersect(elf_group_2.0);
@@ -1,23 +1,16 @@
-use advent_of_code::helpers::parsing::{text_u8, AocLineParsable, AocParsable, ParsingError};
-use nom::{
-    bytes::complete::tag,
-    character::complete::line_ending,
-    combinator::all_consuming,
-    multi::many1,
-    sequence::{separated_pair, tuple},
-    FindSubstring,
+use advent_of_code::helpers::parsing::{
+    expected_error_for_input, parse_complete, parse_table, AocLineFormattable, AocLineParsable,
+    AocParsable, LabelLine, ParsingError,
 };
+use nom::{character::complete::line_ending, multi::many1, sequence::separated_pair};
 
 #[derive(Clone, Debug)]
 struct CargoItem(char);
 
-fn parse_cargo_item_or_none(input: &[u8]) -> Option<CargoItem> {
-    if input[0] == b'[' {
-        Some(CargoItem(input[1] as char))
-    } else if input[0] == b' ' {
-        None
-    } else {
-        panic!("Invalid cargo item");
+fn parse_cargo_item(cell: &[u8]) -> Result<CargoItem, ParsingError<'_>> {
+    match cell {
+        [b'[', item, b']'] => Ok(CargoItem(*item as char)),
+        _ => expected_error_for_input(cell, "cargo item like [A]"),
     }
 }
 
@@ -27,85 +20,32 @@ struct CargoStack(Vec<CargoItem>);
 #[derive(Clone, Debug)]
 struct CargoStage(Vec<CargoStack>);
 
-#[inline]
-fn input_index_for_row_col(row: u8, col: usize, col_count: usize) -> usize {
-    row as usize * (col_count * 4) + (col as usize * 4)
-}
-
-#[inline]
-fn input_slice_for_row_col(input: &[u8], row: u8, col: usize, col_count: usize) -> &[u8] {
-    let start_index = input_index_for_row_col(row, col, col_count);
-    &input[start_index..start_index + 4]
-}
-
 impl AocParsable for CargoStage {
     fn parse_from_string(input: &[u8]) -> Result<(&[u8], Self), ParsingError> {
-        let first_newline = input
-            .find_substring("\n")
-            .expect("Input must have a newline")
-            + 1;
-        assert!(
-            first_newline & 3 == 0,
-            "Columns must divide by 4, was {}",
-            first_newline
-        );
-        let column_count = first_newline / 4;
-
-        let mut row_count = 0;
-        loop {
-            let row_start_index = input_index_for_row_col(row_count, 0, column_count);
-            if input[row_start_index..row_start_index + 2] == [b' ', b'1'] {
-                break;
-            }
-            row_count += 1;
-        }
-
-        let mut columns: Vec<CargoStack> = vec![CargoStack(vec![]); column_count];
-        for row in (0..row_count).rev() {
-            for col in 0..column_count {
-                let input_slice = input_slice_for_row_col(input, row, col, column_count);
-                if let Some(item) = parse_cargo_item_or_none(input_slice) {
-                    columns[col].0.push(item);
-                }
-            }
-        }
-
-        let cargo_stack = CargoStage(columns);
-        Ok((
-            &input[input_index_for_row_col(row_count + 1, 0, column_count)..],
-            cargo_stack,
-        ))
+        let (rest, table) = parse_table(input, LabelLine::Footer)?;
+        let columns = (0..table.column_count())
+            .map(|col| {
+                table
+                    .column(col)
+                    .rev()
+                    .flatten()
+                    .map(parse_cargo_item)
+                    .collect::<Result<_, _>>()
+                    .map(CargoStack)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((rest, CargoStage(columns)))
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, AocLineParsable, AocLineFormattable)]
+#[aoc(pattern = "move {cargo_count} from {from_col} to {to_col}")]
 struct MoveOperation {
     cargo_count: u8,
     from_col: u8,
     to_col: u8,
 }
 
-impl AocLineParsable for MoveOperation {
-    fn parse_from_line(input: &[u8]) -> Result<(&[u8], Self), ParsingError> {
-        let (rest, (_, cargo_count, _, from_col, _, to_col)) = tuple((
-            tag("move "),
-            text_u8(),
-            tag(" from "),
-            text_u8(),
-            tag(" to "),
-            text_u8(),
-        ))(input)?;
-        Ok((
-            rest,
-            MoveOperation {
-                cargo_count,
-                from_col,
-                to_col,
-            },
-        ))
-    }
-}
-
 impl CargoStage {
     fn apply_move_9000(&mut self, move_op: &MoveOperation) {
         let from_cargo_stack = &mut self.0[move_op.from_col as usize - 1].0;
@@ -137,11 +77,14 @@ impl CargoStage {
 }
 
 pub fn part_one(input: &str) -> Option<String> {
-    let (_, (mut stage, move_ops)) = all_consuming(separated_pair(
-        CargoStage::parse_from_string,
-        many1(line_ending),
-        many1(MoveOperation::parse_from_string),
-    ))(input.as_bytes())
+    let (mut stage, move_ops) = parse_complete(
+        input.as_bytes(),
+        separated_pair(
+            CargoStage::parse_from_string,
+            many1(line_ending),
+            many1(MoveOperation::parse_from_string),
+        ),
+    )
     .expect("Invalid input");
 
     for op in move_ops {
@@ -152,11 +95,14 @@ pub fn part_one(input: &str) -> Option<String> {
 }
 
 pub fn part_two(input: &str) -> Option<String> {
-    let (_, (mut stage, move_ops)) = all_consuming(separated_pair(
-        CargoStage::parse_from_string,
-        many1(line_ending),
-        many1(MoveOperation::parse_from_string),
-    ))(input.as_bytes())
+    let (mut stage, move_ops) = parse_complete(
+        input.as_bytes(),
+        separated_pair(
+            CargoStage::parse_from_string,
+            many1(line_ending),
+            many1(MoveOperation::parse_from_string),
+        ),
+    )
     .expect("Invalid input");
 
     for op in move_ops {
@@ -175,6 +121,7 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::helpers::parsing::assert_round_trips;
 
     #[test]
     fn test_part_one() {
@@ -187,4 +134,26 @@ mod tests {
         let input = advent_of_code::read_file("examples", 5);
         assert_eq!(part_two(&input), None);
     }
+
+    #[test]
+    fn test_move_operation_round_trip() {
+        assert_round_trips((1..=50).map(|i| MoveOperation {
+            cargo_count: i,
+            from_col: i % 9 + 1,
+            to_col: 255 - i,
+        }));
+    }
+
+    #[test]
+    fn test_malformed_cargo_item() {
+        let err = parse_complete(
+            b"    [D]\n[N] C  \n 1   2 \n",
+            CargoStage::parse_from_string,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string().lines().next().unwrap(),
+            "expected cargo item like [A] at line 2, column 5"
+        );
+    }
 }
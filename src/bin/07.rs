@@ -1,4 +1,9 @@
-use advent_of_code::helpers::parsing::{line_ending_or_eof, text_u32, ParsingError};
+use std::{cell::RefCell, collections::HashMap};
+
+use advent_of_code::helpers::{
+    parsing::{line_ending_or_eof, text_u32, ParsingError},
+    symbols::{symbol, Symbol, SymbolTable},
+};
 use nom::{
     bytes::complete::tag,
     character::complete::not_line_ending,
@@ -32,30 +37,36 @@ impl File {
 }
 
 #[derive(Debug)]
-enum ChangeDirCommand<'a> {
+enum ChangeDirCommand {
     Root(),
-    Down { directory: &'a [u8] },
+    Down { directory: Symbol },
     Up(),
 }
 
 #[derive(Debug)]
-enum Command<'a> {
-    ChangeDir(ChangeDirCommand<'a>),
+enum Command {
+    ChangeDir(ChangeDirCommand),
     List,
 }
 
-impl<'a> Command<'a> {
-    fn parse(input: &'a [u8]) -> Result<(&'a [u8], Self), ParsingError<'a>> {
+impl Command {
+    fn parse<'a>(
+        input: &'a [u8],
+        symbols: &RefCell<SymbolTable>,
+    ) -> Result<(&'a [u8], Self), ParsingError<'a>> {
         preceded(
             tag("$ "),
             nom::branch::alt((
-                preceded(tag("cd "), not_line_ending).map(|arg: &'a [u8]| {
-                    Command::ChangeDir(match arg {
-                        b"/" => ChangeDirCommand::Root(),
-                        b".." => ChangeDirCommand::Up(),
-                        _ => ChangeDirCommand::Down { directory: arg },
-                    })
-                }),
+                preceded(
+                    tag("cd "),
+                    nom::branch::alt((
+                        tag("/").map(|_| ChangeDirCommand::Root()),
+                        tag("..").map(|_| ChangeDirCommand::Up()),
+                        symbol(symbols, not_line_ending)
+                            .map(|directory| ChangeDirCommand::Down { directory }),
+                    )),
+                )
+                .map(Command::ChangeDir),
                 tag("ls").map(|_| Command::List),
             )),
         )(input)
@@ -63,15 +74,18 @@ impl<'a> Command<'a> {
 }
 
 #[derive(Debug)]
-enum InputLine<'a> {
-    Command(Command<'a>),
+enum InputLine {
+    Command(Command),
     File(File),
 }
 
-impl<'a> InputLine<'a> {
-    fn parse(input: &'a [u8]) -> Result<(&'a [u8], Self), ParsingError<'a>> {
+impl InputLine {
+    fn parse<'a>(
+        input: &'a [u8],
+        symbols: &RefCell<SymbolTable>,
+    ) -> Result<(&'a [u8], Self), ParsingError<'a>> {
         nom::branch::alt((
-            Command::parse.map(InputLine::Command),
+            (|input| Command::parse(input, symbols)).map(InputLine::Command),
             File::parse.map(InputLine::File),
         ))(input)
     }
@@ -79,27 +93,22 @@ impl<'a> InputLine<'a> {
 
 // TREE BUILDING
 
-#[derive(Debug)]
-struct DirectoryNode<'a> {
-    name: &'a [u8],
+/// A directory's name is only needed to find it again, so it lives in the
+/// `ChildDirectories` key rather than in the node.
+#[derive(Debug, Default)]
+struct DirectoryNode {
     files: Vec<File>,
     size: Option<usize>,
 }
 
-impl<'a> DirectoryNode<'a> {
-    fn new_root() -> Self {
-        DirectoryNode {
-            name: "/".as_bytes(),
-            files: vec![],
-            size: None,
-        }
-    }
-}
+/// The subdirectories seen so far, looked up by their parent and name.
+type ChildDirectories = HashMap<(NodeIndex, Symbol), NodeIndex>;
 
-fn change_dir<'a>(
-    graph: &mut petgraph::Graph<DirectoryNode<'a>, ()>,
+fn change_dir(
+    graph: &mut petgraph::Graph<DirectoryNode, ()>,
+    children: &mut ChildDirectories,
     current_dir: NodeIndex,
-    command: ChangeDirCommand<'a>,
+    command: ChangeDirCommand,
 ) -> NodeIndex {
     match command {
         ChangeDirCommand::Up() => graph
@@ -115,38 +124,29 @@ fn change_dir<'a>(
             current_dir
         }
         ChangeDirCommand::Down { directory } => {
-            // Check if the directory already exists
-            graph
-                .edges_directed(current_dir, Direction::Outgoing)
-                .map(|edge_ref| edge_ref.target())
-                .find(|node_index| {
-                    graph
-                        .node_weight(*node_index)
-                        .expect("Target must exist")
-                        .name
-                        == directory
-                })
-                .unwrap_or_else(|| {
-                    let new_dir_index = graph.add_node(DirectoryNode {
-                        name: directory,
-                        files: vec![],
-                        size: None,
-                    });
-                    graph.add_edge(current_dir, new_dir_index, ());
-                    new_dir_index
-                })
+            *children.entry((current_dir, directory)).or_insert_with(|| {
+                let new_dir_index = graph.add_node(DirectoryNode::default());
+                graph.add_edge(current_dir, new_dir_index, ());
+                new_dir_index
+            })
         }
     }
 }
 
-fn parse_from_command_input<'a>(
-    input: &'a [u8],
-) -> (petgraph::Graph<DirectoryNode<'a>, ()>, NodeIndex) {
-    let mut graph = petgraph::Graph::<DirectoryNode<'a>, ()>::new();
-    let root_node_index = graph.add_node(DirectoryNode::new_root());
-    let mut current_node_index = root_node_index.clone();
-
-    let mut input_iterator = iterator(input, terminated(InputLine::parse, line_ending_or_eof()));
+fn parse_from_command_input(input: &[u8]) -> (petgraph::Graph<DirectoryNode, ()>, NodeIndex) {
+    let symbols = RefCell::new(SymbolTable::new());
+    let mut graph = petgraph::Graph::<DirectoryNode, ()>::new();
+    let root_node_index = graph.add_node(DirectoryNode::default());
+    let mut current_node_index = root_node_index;
+    let mut children = ChildDirectories::new();
+
+    let mut input_iterator = iterator(
+        input,
+        terminated(
+            |input| InputLine::parse(input, &symbols),
+            line_ending_or_eof(),
+        ),
+    );
     for input_line in &mut input_iterator {
         match input_line {
             InputLine::File(file) => {
@@ -159,19 +159,24 @@ fn parse_from_command_input<'a>(
                 }
             }
             InputLine::Command(Command::ChangeDir(change_dir_command)) => {
-                current_node_index = change_dir(&mut graph, current_node_index, change_dir_command);
+                current_node_index = change_dir(
+                    &mut graph,
+                    &mut children,
+                    current_node_index,
+                    change_dir_command,
+                );
             }
             InputLine::Command(Command::List) => (),
         }
     }
 
-    return (graph, root_node_index);
+    (graph, root_node_index)
 }
 
-fn calculate_directory_sizes<'a>(
-    mut dir_graph: petgraph::Graph<DirectoryNode<'a>, ()>,
+fn calculate_directory_sizes(
+    mut dir_graph: petgraph::Graph<DirectoryNode, ()>,
     root_node: NodeIndex,
-) -> petgraph::Graph<DirectoryNode<'a>, ()> {
+) -> petgraph::Graph<DirectoryNode, ()> {
     let mut bottom_up_traversal = petgraph::visit::DfsPostOrder::new(&dir_graph, root_node);
     while let Some(visited) = bottom_up_traversal.next(&dir_graph) {
         let dir_node = dir_graph.node_weight(visited).unwrap();
@@ -1,28 +1,15 @@
 use advent_of_code::helpers::{
-    grids::{LineIterator, SquareGrid},
-    parsing::{generic_error_for_input, ParsingResult},
+    grids::{Grid, LineIterator},
+    parsing::parse_grid,
 };
 
-fn parse_tree_heights<'a, const S: usize>(input: &'a [u8]) -> ParsingResult<SquareGrid<u8, S>> {
-    let mut heights = [[0; S]; S];
-    let mut row = 0;
-    let mut col = 0;
-    for c in input {
-        match c {
-            b'\n' => {
-                col = 0;
-                row += 1;
-            }
-            (b'0'..=b'9') => {
-                heights[row][col] = c - b'0';
-                col += 1;
-            }
-            _ => {
-                return generic_error_for_input(input);
-            }
-        }
-    }
-    return Ok((&input[(S * S)..], SquareGrid::from_array(heights)));
+fn parse_tree_heights(input: &[u8]) -> Grid<u8> {
+    parse_grid(input, |c| match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        _ => Err("expected a tree height"),
+    })
+    .unwrap()
+    .into_grid()
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -41,11 +28,7 @@ impl LocationAndHeight {
         }
     }
 
-    fn lookup<const S: usize>(
-        row: usize,
-        col: usize,
-        tree_grid: &SquareGrid<u8, S>,
-    ) -> LocationAndHeight {
+    fn lookup(row: usize, col: usize, tree_grid: &Grid<u8>) -> LocationAndHeight {
         LocationAndHeight {
             row,
             col,
@@ -54,9 +37,9 @@ impl LocationAndHeight {
     }
 }
 
-fn find_visible_trees_along_line<'a, const S: usize>(
-    line_iterator: LineIterator<S>,
-    tree_grid: &'a SquareGrid<u8, S>,
+fn find_visible_trees_along_line<'a>(
+    line_iterator: LineIterator,
+    tree_grid: &'a Grid<u8>,
 ) -> impl Iterator<Item = (usize, usize)> + 'a {
     line_iterator
         .map(|(row, col)| LocationAndHeight::lookup(row, col, tree_grid))
@@ -81,30 +64,36 @@ fn find_visible_trees_along_line<'a, const S: usize>(
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let tree_grid = parse_tree_heights::<99>(input.as_bytes()).unwrap().1;
-    let edge_iterators = (1..98).flat_map(|i| {
-        let line_iterators = [
-            LineIterator::get_line_from_top(i),
-            LineIterator::get_line_from_bottom(i),
-            LineIterator::get_line_from_left(i),
-            LineIterator::get_line_from_right(i),
-        ];
-        line_iterators
-            .into_iter()
-            .flat_map(|line_iterator| find_visible_trees_along_line(line_iterator, &tree_grid))
+    let tree_grid = parse_tree_heights(input.as_bytes());
+    if tree_grid.width() <= 1 || tree_grid.height() <= 1 {
+        // Every tree in a single row or column is on the edge.
+        return Some((tree_grid.width() * tree_grid.height()) as u32);
+    }
+    let dimensions = tree_grid.dimensions();
+    let column_lines = (1..tree_grid.width() - 1).flat_map(|i| {
+        [
+            LineIterator::get_line_from_top(dimensions, i),
+            LineIterator::get_line_from_bottom(dimensions, i),
+        ]
     });
+    let row_lines = (1..tree_grid.height() - 1).flat_map(|i| {
+        [
+            LineIterator::get_line_from_left(dimensions, i),
+            LineIterator::get_line_from_right(dimensions, i),
+        ]
+    });
+    let edge_iterators = column_lines
+        .chain(row_lines)
+        .flat_map(|line_iterator| find_visible_trees_along_line(line_iterator, &tree_grid));
 
-    let mut visibilities = SquareGrid::from_array([[false; 99]; 99]);
+    let mut visibilities = Grid::new_with(tree_grid.width(), tree_grid.height(), false);
     edge_iterators.for_each(|(row, col)| {
         visibilities[row][col] = true;
     });
-    let exterior_count: u32 = 4;
-    let inner_count: u32 = visibilities
-        .iter()
-        .flat_map(|r| r.iter())
-        .map(|v| if *v { 1 } else { 0 })
-        .sum();
-    Some(inner_count + exterior_count)
+    // The lines start on every edge tree except the corners, so only those are left.
+    let corner_count: u32 = 4;
+    let inner_count: u32 = visibilities.cells().map(|v| if *v { 1 } else { 0 }).sum();
+    Some(inner_count + corner_count)
 }
 
 #[derive(Debug)]
@@ -131,13 +120,14 @@ impl TreeView {
     }
 }
 
-fn view_distances_in_direction<const S: usize>(
-    tree_grid: &SquareGrid<u8, S>,
-    line_iter_fn: &fn(usize) -> LineIterator<S>,
-) -> SquareGrid<u32, S> {
-    let mut view_distances = SquareGrid::new_with(0u32);
-    for i in 0..S {
-        line_iter_fn(i)
+fn view_distances_in_direction(
+    tree_grid: &Grid<u8>,
+    line_iter_fn: &fn((usize, usize), usize) -> LineIterator,
+    lines: usize,
+) -> Grid<u32> {
+    let mut view_distances = Grid::new_with(tree_grid.width(), tree_grid.height(), 0u32);
+    for i in 0..lines {
+        line_iter_fn(tree_grid.dimensions(), i)
             .enumerate()
             .scan(
                 TreeView::new(),
@@ -154,22 +144,24 @@ fn view_distances_in_direction<const S: usize>(
     view_distances
 }
 
-pub fn part_two<const S: usize>(input: &str) -> Option<u32> {
-    let tree_grid = parse_tree_heights::<S>(input.as_bytes()).unwrap().1;
-    let line_iterator_fns = [
-        |i| LineIterator::<S>::get_line_from_top(i),
-        |i| LineIterator::<S>::get_line_from_bottom(i),
-        |i| LineIterator::<S>::get_line_from_left(i),
-        |i| LineIterator::<S>::get_line_from_right(i),
+pub fn part_two(input: &str) -> Option<u32> {
+    let tree_grid = parse_tree_heights(input.as_bytes());
+    if tree_grid.width() == 0 || tree_grid.height() == 0 {
+        return Some(0);
+    }
+    let line_iterator_fns: [(fn((usize, usize), usize) -> LineIterator, usize); 4] = [
+        (LineIterator::get_line_from_top, tree_grid.width()),
+        (LineIterator::get_line_from_bottom, tree_grid.width()),
+        (LineIterator::get_line_from_left, tree_grid.height()),
+        (LineIterator::get_line_from_right, tree_grid.height()),
     ];
     Some(
         *line_iterator_fns
             .iter()
-            .map(|iter_fn| view_distances_in_direction(&tree_grid, iter_fn))
+            .map(|(iter_fn, lines)| view_distances_in_direction(&tree_grid, iter_fn, *lines))
             .reduce(|a, b| a * b)
             .expect("No view grids generated")
-            .iter()
-            .flat_map(|row| row.iter())
+            .cells()
             .max()
             .expect("View must have a maximum"),
     )
@@ -178,18 +170,35 @@ pub fn part_two<const S: usize>(input: &str) -> Option<u32> {
 fn main() {
     let input = &advent_of_code::read_file("inputs", 8);
     advent_of_code::solve!(1, part_one, input);
-    let part_two_sized = part_two::<99>;
-    advent_of_code::solve!(2, part_two_sized, input);
+    advent_of_code::solve!(2, part_two, input);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 8);
+        assert_eq!(part_one(&input), Some(21));
+    }
+
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 8);
-        assert_eq!(part_two::<5>(&input).unwrap(), 8);
+        assert_eq!(part_two(&input), Some(8));
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(part_one(""), Some(0));
+        assert_eq!(part_two(""), Some(0));
     }
-}
 
+    #[test]
+    fn test_single_row_and_column() {
+        assert_eq!(part_one("5\n"), Some(1));
+        assert_eq!(part_one("123\n"), Some(3));
+        assert_eq!(part_one("1\n2\n3\n"), Some(3));
+    }
+}
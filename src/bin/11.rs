@@ -4,7 +4,7 @@ use std::{cell::RefCell, mem::swap};
 
 use advent_of_code::helpers::iter::AocIteratorExtensions;
 use advent_of_code::helpers::parsing::{
-    iterate_all, line_ending_or_eof, text_u8, text_usize, AocLineParsable, ParsingResult,
+    iterate_blocks, text_u8, text_usize, AocBlockParsable, ParsingResult,
 };
 use nom::{
     bytes::streaming::tag,
@@ -74,8 +74,8 @@ struct Monkey {
     inspection_count: u32,
 }
 
-impl AocLineParsable for Monkey {
-    fn parse_from_line<'a>(input: &'a [u8]) -> ParsingResult<Monkey> {
+impl AocBlockParsable for Monkey {
+    fn parse_from_block<'a>(input: &'a [u8]) -> ParsingResult<Monkey> {
         map(
             tuple((
                 tag("Monkey "),
@@ -90,9 +90,8 @@ impl AocLineParsable for Monkey {
                 text_u8(),
                 tag("\n    If false: throw to monkey "),
                 text_u8(),
-                line_ending_or_eof(),
             )),
-            |(_, _, _, starting_items, _, operation, _, test, _, t, _, f, _)| Monkey {
+            |(_, _, _, starting_items, _, operation, _, test, _, t, _, f)| Monkey {
                 items: starting_items,
                 operation,
                 test_divisibility: test,
@@ -153,7 +152,7 @@ impl MonkeyGaggle {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let gaggle = MonkeyGaggle::new(&mut iterate_all(input.as_bytes()));
+    let gaggle = MonkeyGaggle::new(iterate_blocks(input.as_bytes()).map(Result::unwrap));
     for _i in 0..20 {
         gaggle.simulate_round(NonZeroU8::new(3).unwrap());
     }
@@ -166,7 +165,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let gaggle = MonkeyGaggle::new(&mut iterate_all(input.as_bytes()));
+    let gaggle = MonkeyGaggle::new(iterate_blocks(input.as_bytes()).map(Result::unwrap));
     for _i in 0..10000 {
         gaggle.simulate_round(NonZeroU8::new(1).unwrap());
     }
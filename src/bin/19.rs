@@ -1,16 +1,11 @@
 #![feature(portable_simd)]
-use advent_of_code::helpers::parsing::{
-    iterate_all, text_u8, AocLineParsable, AocParsable, ParsingResult,
-};
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    combinator::map,
-    sequence::{separated_pair, terminated, tuple},
+use advent_of_code::helpers::{
+    blueprints::{self, RobotCost},
+    parsing::par_parse_all,
 };
 use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use std::{
-    ops::{Add, Index, IndexMut},
+    ops::{Index, IndexMut},
     simd::{i16x4, SimdInt},
 };
 use strum::IntoEnumIterator;
@@ -24,7 +19,7 @@ enum Material {
     Geode = 3,
 }
 
-#[derive(Clone, Copy, Default, Debug)]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
 struct MaterialMap<T: Clone + Copy + Default + Sized>([T; 4]);
 
 impl<T: Clone + Copy + Default + Sized> Index<Material> for MaterialMap<T> {
@@ -43,17 +38,9 @@ impl<T: Clone + Copy + Default + Sized> IndexMut<Material> for MaterialMap<T> {
 
 // Parsing
 
-#[derive(Clone, Copy, Default, Debug)]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
 struct MaterialCost(i16x4);
 
-impl Add for MaterialCost {
-    type Output = MaterialCost;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        MaterialCost(self.0 + rhs.0)
-    }
-}
-
 impl Index<Material> for MaterialCost {
     type Output = i16;
 
@@ -62,104 +49,40 @@ impl Index<Material> for MaterialCost {
     }
 }
 
-impl MaterialCost {
-    fn parse_single_cost(input: &[u8]) -> ParsingResult<MaterialCost> {
-        alt((
-            map(terminated(text_u8(), tag(" ore")), |c| {
-                MaterialCost(i16x4::from_array([c as i16, 0, 0, 0]))
-            }),
-            map(terminated(text_u8(), tag(" clay")), |c| {
-                MaterialCost(i16x4::from_array([0, c as i16, 0, 0]))
-            }),
-            map(terminated(text_u8(), tag(" obsidian")), |c| {
-                MaterialCost(i16x4::from_array([0, 0, c as i16, 0]))
-            }),
-        ))(input)
+impl From<RobotCost> for MaterialCost {
+    fn from(cost: RobotCost) -> Self {
+        MaterialCost(i16x4::from_array(cost.0.map(|amount| amount as i16)))
     }
 }
 
-impl AocParsable for MaterialCost {
-    fn parse_from_string(input: &[u8]) -> ParsingResult<MaterialCost> {
-        alt((
-            map(
-                separated_pair(
-                    MaterialCost::parse_single_cost,
-                    tag(" and "),
-                    MaterialCost::parse_single_cost,
-                ),
-                |(a, b)| a + b,
-            ),
-            MaterialCost::parse_single_cost,
-        ))(input)
-    }
-}
-
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct BlueprintCosts {
     blueprint_number: u8,
     robot_costs: MaterialMap<MaterialCost>,
     max_costs_per_material: MaterialCost,
 }
 
-impl AocLineParsable for BlueprintCosts {
-    fn parse_from_line(
-        input: &[u8],
-    ) -> Result<(&[u8], Self), advent_of_code::helpers::parsing::ParsingError> {
-        map(
-            tuple((
-                tag("Blueprint "),
-                text_u8(),
-                tag(": Each ore robot costs "),
-                MaterialCost::parse_from_string,
-                tag(". Each clay robot costs "),
-                MaterialCost::parse_from_string,
-                tag(". Each obsidian robot costs "),
-                MaterialCost::parse_from_string,
-                tag(". Each geode robot costs "),
-                MaterialCost::parse_from_string,
-                tag("."),
-            )),
-            |(
-                _,
-                blueprint_number,
-                _,
-                ore_robot,
-                _,
-                clay_robot,
-                _,
-                obsidian_robot,
-                _,
-                geode_robot,
-                _,
-            )| {
-                let raw_robot_costs = [ore_robot, clay_robot, obsidian_robot, geode_robot];
-                let max_costs_per_material = MaterialCost(i16x4::from_array([
-                    (0..4)
-                        .map(|robot_index| raw_robot_costs[robot_index].0[Material::Ore as usize])
-                        .max()
-                        .unwrap(),
-                    (0..4)
-                        .map(|robot_index| raw_robot_costs[robot_index].0[Material::Clay as usize])
-                        .max()
-                        .unwrap(),
-                    (0..4)
-                        .map(|robot_index| {
-                            raw_robot_costs[robot_index].0[Material::Obsidian as usize]
-                        })
-                        .max()
-                        .unwrap(),
-                    0i16,
-                ]));
-                BlueprintCosts {
-                    blueprint_number,
-                    robot_costs: MaterialMap(raw_robot_costs),
-                    max_costs_per_material,
-                }
-            },
-        )(input)
+impl From<blueprints::BlueprintCosts> for BlueprintCosts {
+    fn from(blueprint: blueprints::BlueprintCosts) -> Self {
+        let [ore, clay, obsidian, _] = blueprint.max_costs();
+        BlueprintCosts {
+            blueprint_number: blueprint.number,
+            robot_costs: MaterialMap(blueprint.robot_costs().map(MaterialCost::from)),
+            max_costs_per_material: MaterialCost(i16x4::from_array([
+                ore as i16,
+                clay as i16,
+                obsidian as i16,
+                0,
+            ])),
+        }
     }
 }
 
+fn parse_blueprints(input: &str) -> Vec<BlueprintCosts> {
+    let blueprints: Vec<blueprints::BlueprintCosts> = par_parse_all(input.as_bytes()).unwrap();
+    blueprints.into_iter().map(BlueprintCosts::from).collect()
+}
+
 // Simulating
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -260,7 +183,7 @@ fn is_potential_next_robot(
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let blueprint_options: Vec<BlueprintCosts> = iterate_all(input.as_bytes()).collect();
+    let blueprint_options = parse_blueprints(input);
     Some(
         blueprint_options
             .par_iter()
@@ -270,7 +193,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let blueprint_options: Vec<BlueprintCosts> = iterate_all(input.as_bytes()).collect();
+    let blueprint_options = parse_blueprints(input);
     blueprint_options
         .par_iter()
         .take(3)
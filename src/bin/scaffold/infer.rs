@@ -0,0 +1,1125 @@
+use std::fmt::{Display, Write};
+
+/// nom's `tuple` combinator is only implemented for up to 21 parsers.
+const MAX_TUPLE_SIZE: usize = 21;
+
+/// rustfmt's line width, which generated code should stay within.
+const MAX_WIDTH: usize = 100;
+
+/// Long literals are split into several `tag`s so that lines stay short.
+const MAX_TAG_CHARS: usize = 48;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Words that say nothing about the number next to them, so the following word is used instead.
+const STOP_WORDS: &[&str] = &["a", "an", "and", "at", "by", "is", "of", "or", "the"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Structure {
+    Grid,
+    Lines,
+    Blocks,
+}
+
+impl Display for Structure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Structure::Grid => "grid",
+            Structure::Lines => "one record per line",
+            Structure::Blocks => "blank-line separated blocks",
+        })
+    }
+}
+
+/// One piece of a line of the example, before lines are compared with each other.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Lexeme {
+    Number {
+        signed: bool,
+    },
+    /// Two or more numbers with the same separator between each of them.
+    List {
+        signed: bool,
+        separator: String,
+    },
+    Word(String),
+    Space(String),
+    Punct(char),
+}
+
+impl Lexeme {
+    fn text(&self) -> Option<String> {
+        match self {
+            Lexeme::Word(text) | Lexeme::Space(text) => Some(text.clone()),
+            Lexeme::Punct(c) => Some(c.to_string()),
+            _ => None,
+        }
+    }
+
+    fn is_char(&self) -> bool {
+        match self {
+            Lexeme::Word(word) => word.chars().count() == 1,
+            Lexeme::Punct(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Text that differs between records and is kept as a field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Capture {
+    Char,
+    Alpha,
+    AlphaNumeric,
+    /// A line with no usable structure, kept whole.
+    Line,
+    /// A block with no usable structure, kept as its lines.
+    Lines,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Literal(String),
+    /// Runs of spaces whose width varies between records.
+    Spaces,
+    Number {
+        signed: bool,
+    },
+    /// Numbers separated by `separator`, where `"\n"` means one number per line.
+    List {
+        signed: bool,
+        separator: String,
+    },
+    Capture(Capture),
+}
+
+impl Token {
+    fn is_field(&self) -> bool {
+        !matches!(self, Token::Literal(_) | Token::Spaces)
+    }
+}
+
+fn normalize(example: &str) -> String {
+    example
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+pub fn detect_structure(example: &str) -> Structure {
+    let example = normalize(example);
+    if example.contains("\n\n") {
+        return Structure::Blocks;
+    }
+
+    let lines: Vec<&str> = example.lines().collect();
+    let width = lines.first().map_or(0, |line| line.len());
+    let is_grid = lines.len() > 1
+        && width > 1
+        && lines
+            .iter()
+            .all(|line| line.len() == width && !line.contains(' '))
+        && !has_numeric_fields(&unify_lines(&lines));
+    if is_grid {
+        Structure::Grid
+    } else {
+        Structure::Lines
+    }
+}
+
+/// Lines like `2-4,6-8` line up into numbers with punctuation between them, so
+/// they are records rather than rows of grid cells, even if they are all as wide.
+fn has_numeric_fields(tokens: &[Token]) -> bool {
+    tokens
+        .iter()
+        .any(|token| matches!(token, Token::Number { .. } | Token::List { .. }))
+        && tokens
+            .iter()
+            .any(|token| matches!(token, Token::Literal(_)))
+}
+
+fn lex(line: &str) -> Vec<Lexeme> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut lexemes = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        let starts_signed_number = chars[i] == '-'
+            && chars.get(i + 1).is_some_and(char::is_ascii_digit)
+            && (i == 0 || !chars[i - 1].is_ascii_alphanumeric());
+        if chars[i].is_ascii_digit() || starts_signed_number {
+            i += 1;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            lexemes.push(Lexeme::Number {
+                signed: starts_signed_number,
+            });
+        } else if chars[i].is_ascii_alphabetic() {
+            while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                i += 1;
+            }
+            lexemes.push(Lexeme::Word(chars[start..i].iter().collect()));
+        } else if chars[i] == ' ' {
+            while i < chars.len() && chars[i] == ' ' {
+                i += 1;
+            }
+            lexemes.push(Lexeme::Space(chars[start..i].iter().collect()));
+        } else {
+            lexemes.push(Lexeme::Punct(chars[i]));
+            i += 1;
+        }
+    }
+    lexemes
+}
+
+/// Replaces runs like `79, 98, 12` with a single `List`, for lines that only
+/// differ in how many numbers they hold.
+fn collapse_lists(lexemes: &[Lexeme]) -> Vec<Lexeme> {
+    let mut collapsed = vec![];
+    let mut i = 0;
+    while i < lexemes.len() {
+        let Lexeme::Number { mut signed } = lexemes[i] else {
+            collapsed.push(lexemes[i].clone());
+            i += 1;
+            continue;
+        };
+        let mut separator: Option<String> = None;
+        let mut end = i + 1;
+        loop {
+            let mut j = end;
+            let mut next_separator = String::new();
+            while let Some(Lexeme::Space(_) | Lexeme::Punct(_)) = lexemes.get(j) {
+                next_separator += &lexemes[j].text().unwrap();
+                j += 1;
+            }
+            let same_separator = separator.as_ref().is_none_or(|s| *s == next_separator);
+            match lexemes.get(j) {
+                Some(Lexeme::Number {
+                    signed: next_signed,
+                }) if !next_separator.is_empty() && same_separator => {
+                    signed |= next_signed;
+                    separator = Some(next_separator);
+                    end = j + 1;
+                }
+                _ => break,
+            }
+        }
+        collapsed.push(match separator {
+            Some(separator) => Lexeme::List { signed, separator },
+            None => Lexeme::Number { signed },
+        });
+        i = end;
+    }
+    collapsed
+}
+
+/// Finds a token that matches what every record has at the same position.
+fn unify(lexemes: &[&Lexeme]) -> Option<Token> {
+    let signed = lexemes.iter().any(|lexeme| {
+        matches!(
+            lexeme,
+            Lexeme::Number { signed: true } | Lexeme::List { signed: true, .. }
+        )
+    });
+    let mut separators = lexemes.iter().filter_map(|lexeme| match lexeme {
+        Lexeme::List { separator, .. } => Some(separator),
+        _ => None,
+    });
+    let all = |f: fn(&Lexeme) -> bool| lexemes.iter().all(|lexeme| f(lexeme));
+
+    if all(|lexeme| matches!(lexeme, Lexeme::Number { .. } | Lexeme::List { .. })) {
+        return match separators.next() {
+            None => Some(Token::Number { signed }),
+            Some(separator) if separators.all(|other| other == separator) => Some(Token::List {
+                signed,
+                separator: separator.clone(),
+            }),
+            Some(_) => None,
+        };
+    }
+    if lexemes
+        .iter()
+        .all(|lexeme| lexeme.text().is_some() && *lexeme == lexemes[0])
+    {
+        return lexemes[0].text().map(Token::Literal);
+    }
+    if all(|lexeme| matches!(lexeme, Lexeme::Space(_))) {
+        Some(Token::Spaces)
+    } else if all(Lexeme::is_char) {
+        Some(Token::Capture(Capture::Char))
+    } else if all(|lexeme| matches!(lexeme, Lexeme::Word(_))) {
+        Some(Token::Capture(Capture::Alpha))
+    } else if all(|lexeme| matches!(lexeme, Lexeme::Word(_) | Lexeme::Number { .. })) {
+        Some(Token::Capture(Capture::AlphaNumeric))
+    } else {
+        None
+    }
+}
+
+fn unify_sequences(sequences: &[Vec<Lexeme>]) -> Option<Vec<Token>> {
+    let len = sequences[0].len();
+    if sequences.iter().any(|sequence| sequence.len() != len) {
+        return None;
+    }
+    (0..len)
+        .map(|i| {
+            unify(
+                &sequences
+                    .iter()
+                    .map(|sequence| &sequence[i])
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
+/// Tokens for the lines that are at the same place in every record. Lines that
+/// can't be lined up token by token are kept whole.
+fn unify_lines(lines: &[&str]) -> Vec<Token> {
+    let lexed: Vec<Vec<Lexeme>> = lines.iter().map(|line| lex(line)).collect();
+    unify_sequences(&lexed)
+        .or_else(|| {
+            let collapsed: Vec<Vec<Lexeme>> = lexed.iter().map(|l| collapse_lists(l)).collect();
+            unify_sequences(&collapsed)
+        })
+        .unwrap_or_else(|| vec![Token::Capture(Capture::Line)])
+}
+
+enum LineGroup<'a> {
+    /// Consecutive lines that each hold nothing but a number.
+    Numbers {
+        signed: bool,
+    },
+    Line(&'a str),
+}
+
+fn group_number_lines<'a>(lines: &[&'a str]) -> Vec<LineGroup<'a>> {
+    let mut groups: Vec<LineGroup> = vec![];
+    for line in lines {
+        match (lex(line).as_slice(), groups.last_mut()) {
+            (
+                [Lexeme::Number { signed }],
+                Some(LineGroup::Numbers {
+                    signed: group_signed,
+                }),
+            ) => {
+                *group_signed |= signed;
+            }
+            ([Lexeme::Number { signed }], _) => groups.push(LineGroup::Numbers { signed: *signed }),
+            _ => groups.push(LineGroup::Line(line)),
+        }
+    }
+    groups
+}
+
+/// Joins the tokens of consecutive lines and merges neighbouring literals.
+fn join_lines(lines: impl IntoIterator<Item = Vec<Token>>) -> Vec<Token> {
+    let mut tokens: Vec<Token> = vec![];
+    for (i, line) in lines.into_iter().enumerate() {
+        let newline = (i > 0).then(|| Token::Literal("\n".to_string()));
+        for token in newline.into_iter().chain(line) {
+            match (tokens.last_mut(), token) {
+                (Some(Token::Literal(previous)), Token::Literal(literal)) => *previous += &literal,
+                (_, token) => tokens.push(token),
+            }
+        }
+    }
+    tokens
+}
+
+/// Lines up the records of the example. Records with the same number of lines are
+/// compared line by line, otherwise runs of number-only lines become lists.
+/// Anything that still doesn't line up is captured as text, so the inferred
+/// parser always accepts the example it came from.
+fn infer_tokens(records: &[&str]) -> Vec<Token> {
+    let lines: Vec<Vec<&str>> = records
+        .iter()
+        .map(|record| record.lines().collect())
+        .collect();
+    let line_count = lines[0].len();
+    if lines.iter().all(|record| record.len() == line_count) {
+        return join_lines(
+            (0..line_count)
+                .map(|i| unify_lines(&lines.iter().map(|record| record[i]).collect::<Vec<_>>())),
+        );
+    }
+
+    let groups: Vec<Vec<LineGroup>> = lines
+        .iter()
+        .map(|record| group_number_lines(record))
+        .collect();
+    let group_count = groups[0].len();
+    if groups.iter().any(|record| record.len() != group_count) {
+        return vec![Token::Capture(Capture::Lines)];
+    }
+    let mut joined = vec![];
+    for i in 0..group_count {
+        let column: Vec<&LineGroup> = groups.iter().map(|record| &record[i]).collect();
+        if let Some(group_lines) = column
+            .iter()
+            .map(|group| match group {
+                LineGroup::Line(line) => Some(*line),
+                LineGroup::Numbers { .. } => None,
+            })
+            .collect::<Option<Vec<&str>>>()
+        {
+            joined.push(unify_lines(&group_lines));
+        } else if column
+            .iter()
+            .all(|group| matches!(group, LineGroup::Numbers { .. }))
+        {
+            let signed = column
+                .iter()
+                .any(|group| matches!(group, LineGroup::Numbers { signed: true }));
+            joined.push(vec![Token::List {
+                signed,
+                separator: "\n".to_string(),
+            }]);
+        } else {
+            return vec![Token::Capture(Capture::Lines)];
+        }
+    }
+    join_lines(joined)
+}
+
+fn is_word_char(c: &char) -> bool {
+    c.is_ascii_alphabetic() || *c == '_'
+}
+
+fn first_word(literal: &str) -> Option<String> {
+    let word: String = literal
+        .chars()
+        .skip_while(|c| *c == ' ')
+        .take_while(is_word_char)
+        .collect();
+    Some(word.to_ascii_lowercase()).filter(|word| !word.is_empty())
+}
+
+fn last_word(literal: &str) -> Option<String> {
+    let word: String = literal
+        .chars()
+        .rev()
+        .skip_while(|c| !c.is_ascii_alphabetic())
+        .take_while(is_word_char)
+        .collect::<Vec<char>>()
+        .into_iter()
+        .rev()
+        .collect();
+    Some(word.to_ascii_lowercase()).filter(|word| !word.is_empty())
+}
+
+fn literal_at(tokens: &[Token], index: Option<usize>) -> Option<&str> {
+    match tokens.get(index?) {
+        Some(Token::Literal(literal)) => Some(literal),
+        _ => None,
+    }
+}
+
+/// Names each field after the last word of the literal text in front of it,
+/// e.g. `move 1 from 2 to 3` becomes `move_value`, `from` and `to`. Filler words
+/// like `and` fall back to the word right after the field instead.
+fn field_names(tokens: &[Token]) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    let mut base: Option<String> = None;
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_field() {
+            continue;
+        }
+        let preceding = literal_at(tokens, i.checked_sub(1)).and_then(last_word);
+        let following = literal_at(tokens, Some(i + 1)).and_then(first_word);
+        let word = match preceding {
+            Some(word) if STOP_WORDS.contains(&word.as_str()) => following.or(Some(word)),
+            None if base.is_none() => following,
+            preceding => preceding,
+        };
+        if let Some(word) = word {
+            base = Some(if RUST_KEYWORDS.contains(&word.as_str()) {
+                format!("{}_value", word)
+            } else {
+                word
+            });
+        }
+        let base = base.clone().unwrap_or_else(|| {
+            match token {
+                Token::List { .. } => "values",
+                Token::Capture(Capture::Line) => "line",
+                Token::Capture(Capture::Lines) => "lines",
+                _ => "value",
+            }
+            .to_string()
+        });
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while names.contains(&name) {
+            name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        names.push(name);
+    }
+    names
+}
+
+fn render_pattern(patterns: &[String]) -> String {
+    if patterns.len() == 1 {
+        format!("({},)", patterns[0])
+    } else {
+        format!("({})", patterns.join(", "))
+    }
+}
+
+/// Renders parsers as a (possibly nested) `tuple`, along with the top-level
+/// elements of the pattern that destructures its output.
+fn render_tuple(parsers: &[(String, String)], indent: usize) -> (String, Vec<String>) {
+    let pad = " ".repeat(indent);
+    let (flat, nested) = if parsers.len() > MAX_TUPLE_SIZE {
+        parsers.split_at(MAX_TUPLE_SIZE - 1)
+    } else {
+        (parsers, &[][..])
+    };
+
+    let mut expr = "tuple((\n".to_string();
+    let mut patterns: Vec<String> = flat.iter().map(|(_, pattern)| pattern.clone()).collect();
+    for (parser, _) in flat {
+        writeln!(expr, "{}    {},", pad, parser).unwrap();
+    }
+    if !nested.is_empty() {
+        let (nested_expr, nested_patterns) = render_tuple(nested, indent + 4);
+        writeln!(expr, "{}    {},", pad, nested_expr).unwrap();
+        patterns.push(render_pattern(&nested_patterns));
+    }
+    write!(expr, "{}))", pad).unwrap();
+    (expr, patterns)
+}
+
+fn iterator_name(structure: Structure) -> &'static str {
+    match structure {
+        Structure::Blocks => "iterate_blocks",
+        _ => "iterate_all",
+    }
+}
+
+fn trait_name(structure: Structure) -> &'static str {
+    match structure {
+        Structure::Blocks => "AocBlockParsable",
+        _ => "AocLineParsable",
+    }
+}
+
+/// Formats a `use` the way rustfmt does: on one line if it fits, one item per
+/// line if it groups nested lists, and otherwise wrapped at 100 columns.
+fn render_use(path: &str, items: &[String]) -> String {
+    let line = format!("use {}::{{{}}};\n", path, items.join(", "));
+    if items.iter().any(|item| item.contains('{')) {
+        let mut vertical = format!("use {}::{{\n", path);
+        for item in items {
+            writeln!(vertical, "    {},", item).unwrap();
+        }
+        return vertical + "};\n";
+    }
+    if line.len() <= MAX_WIDTH + 1 {
+        return line;
+    }
+    let mut wrapped = format!("use {}::{{\n   ", path);
+    let mut width = 3;
+    for item in items {
+        if width + 1 + item.len() + 1 > MAX_WIDTH {
+            wrapped += "\n   ";
+            width = 3;
+        }
+        write!(wrapped, " {},", item).unwrap();
+        width += 1 + item.len() + 1;
+    }
+    wrapped + "\n};\n"
+}
+
+fn has_capture(tokens: &[Token], captures: &[Capture]) -> bool {
+    tokens
+        .iter()
+        .any(|token| matches!(token, Token::Capture(capture) if captures.contains(capture)))
+}
+
+/// Whether the `text` helper, which turns matched bytes into a `String`, is needed.
+fn needs_text(tokens: &[Token]) -> bool {
+    has_capture(
+        tokens,
+        &[
+            Capture::Alpha,
+            Capture::AlphaNumeric,
+            Capture::Line,
+            Capture::Lines,
+        ],
+    )
+}
+
+fn render_imports(tokens: &[Token], structure: Structure) -> String {
+    let is_signed = |token: &Token| {
+        matches!(
+            token,
+            Token::Number { signed: true } | Token::List { signed: true, .. }
+        )
+    };
+    let is_unsigned = |token: &Token| {
+        matches!(
+            token,
+            Token::Number { signed: false } | Token::List { signed: false, .. }
+        )
+    };
+
+    let mut helpers = vec![iterator_name(structure)];
+    if tokens.iter().any(is_signed) {
+        helpers.push("text_i32");
+    }
+    if tokens.iter().any(is_unsigned) {
+        helpers.push("text_u32");
+    }
+    helpers.extend([trait_name(structure), "ParsingResult"]);
+    let helpers: Vec<String> = helpers.into_iter().map(String::from).collect();
+
+    let literals = tokens.iter().filter_map(|token| match token {
+        Token::Literal(literal) => Some(literal),
+        _ => None,
+    });
+    let has_tag = literals
+        .clone()
+        .any(|literal| literal.split('\n').any(|part| !part.is_empty()))
+        || tokens
+            .iter()
+            .any(|token| matches!(token, Token::List { separator, .. } if separator != "\n"));
+    let has_line_ending = literals.clone().any(|literal| literal.contains('\n'))
+        || has_capture(tokens, &[Capture::Lines])
+        || tokens
+            .iter()
+            .any(|token| matches!(token, Token::List { separator, .. } if separator == "\n"));
+    let has_list = has_capture(tokens, &[Capture::Lines])
+        || tokens
+            .iter()
+            .any(|token| matches!(token, Token::List { .. }));
+
+    let mut characters = vec![];
+    for (name, used) in [
+        ("alpha1", has_capture(tokens, &[Capture::Alpha])),
+        (
+            "alphanumeric1",
+            has_capture(tokens, &[Capture::AlphaNumeric]),
+        ),
+        ("anychar", has_capture(tokens, &[Capture::Char])),
+        ("line_ending", has_line_ending),
+        (
+            "not_line_ending",
+            has_capture(tokens, &[Capture::Line, Capture::Lines]),
+        ),
+        ("space1", tokens.contains(&Token::Spaces)),
+    ] {
+        if used {
+            characters.push(name);
+        }
+    }
+    let mut nom_items = vec![];
+    if has_tag {
+        nom_items.push("bytes::complete::tag".to_string());
+    }
+    match characters.as_slice() {
+        [] => {}
+        [single] => nom_items.push(format!("character::complete::{}", single)),
+        several => nom_items.push(format!("character::complete::{{{}}}", several.join(", "))),
+    }
+    nom_items.push("combinator::map".to_string());
+    if has_list {
+        nom_items.push("multi::separated_list1".to_string());
+    }
+    nom_items.push("sequence::tuple".to_string());
+
+    render_use("advent_of_code::helpers::parsing", &helpers) + &render_use("nom", &nom_items)
+}
+
+/// Splits a literal into `tag`s short enough to keep lines within the width
+/// limit, with a `line_ending` for each newline so that `\r\n` is accepted too.
+fn literal_parsers(literal: &str) -> Vec<String> {
+    let mut parsers = vec![];
+    for (i, part) in literal.split('\n').enumerate() {
+        if i > 0 {
+            parsers.push("line_ending".to_string());
+        }
+        let chars: Vec<char> = part.chars().collect();
+        for chunk in chars.chunks(MAX_TAG_CHARS) {
+            parsers.push(format!("tag({:?})", chunk.iter().collect::<String>()));
+        }
+    }
+    parsers
+}
+
+fn number_parser(signed: bool) -> &'static str {
+    if signed {
+        "text_i32()"
+    } else {
+        "text_u32()"
+    }
+}
+
+/// The parser for a field, along with the type of its value.
+fn field_parser(token: &Token) -> (String, String) {
+    let number_type = |signed: bool| if signed { "i32" } else { "u32" };
+    match token {
+        Token::Number { signed } => (
+            number_parser(*signed).to_string(),
+            number_type(*signed).to_string(),
+        ),
+        Token::List { signed, separator } => {
+            let separator = if separator == "\n" {
+                "line_ending".to_string()
+            } else {
+                format!("tag({:?})", separator)
+            };
+            (
+                format!("separated_list1({}, {})", separator, number_parser(*signed)),
+                format!("Vec<{}>", number_type(*signed)),
+            )
+        }
+        Token::Capture(Capture::Char) => ("anychar".to_string(), "char".to_string()),
+        Token::Capture(Capture::Alpha) => ("text(alpha1)".to_string(), "String".to_string()),
+        Token::Capture(Capture::AlphaNumeric) => {
+            ("text(alphanumeric1)".to_string(), "String".to_string())
+        }
+        Token::Capture(Capture::Line) => {
+            ("text(not_line_ending)".to_string(), "String".to_string())
+        }
+        Token::Capture(Capture::Lines) => (
+            "separated_list1(line_ending, text(not_line_ending))".to_string(),
+            "Vec<String>".to_string(),
+        ),
+        Token::Literal(_) | Token::Spaces => unreachable!("Not a field: {:?}", token),
+    }
+}
+
+const TEXT_HELPER: &str = "\
+fn text<'a>(
+    parser: impl FnMut(&'a [u8]) -> ParsingResult<'a, &'a [u8]>,
+) -> impl FnMut(&'a [u8]) -> ParsingResult<'a, String> {
+    map(parser, |text: &[u8]| {
+        String::from_utf8_lossy(text).into_owned()
+    })
+}
+";
+
+fn render_record(tokens: &[Token], structure: Structure) -> String {
+    let names = field_names(tokens);
+    let mut names_iter = names.iter();
+    let mut fields: Vec<(String, String)> = vec![];
+    let mut parsers: Vec<(String, String)> = vec![];
+    for token in tokens {
+        match token {
+            Token::Literal(literal) => parsers.extend(
+                literal_parsers(literal)
+                    .into_iter()
+                    .map(|parser| (parser, "_".to_string())),
+            ),
+            Token::Spaces => parsers.push(("space1".to_string(), "_".to_string())),
+            field => {
+                let name = names_iter.next().unwrap().clone();
+                let (parser, field_type) = field_parser(field);
+                parsers.push((parser, name.clone()));
+                fields.push((name, field_type));
+            }
+        }
+    }
+
+    let mut record = String::new();
+    if needs_text(tokens) {
+        record += TEXT_HELPER;
+        record += "\n";
+    }
+    record += "#[derive(Debug)]\nstruct Record {\n";
+    for (name, field_type) in &fields {
+        writeln!(record, "    {}: {},", name, field_type).unwrap();
+    }
+    record += "}\n\n";
+
+    let (tuple_expr, patterns) = render_tuple(&parsers, 12);
+    let method = match structure {
+        Structure::Blocks => "parse_from_block",
+        _ => "parse_from_line",
+    };
+    writeln!(record, "impl {} for Record {{", trait_name(structure)).unwrap();
+    writeln!(
+        record,
+        "    fn {}(input: &[u8]) -> ParsingResult<'_, Record> {{",
+        method
+    )
+    .unwrap();
+    record += "        map(\n";
+    writeln!(record, "            {},", tuple_expr).unwrap();
+    let closure_head = format!("            |{}| Record {{", render_pattern(&patterns));
+    if closure_head.len() > MAX_WIDTH {
+        record += "            |(\n";
+        for field_pattern in patterns.iter() {
+            writeln!(record, "                {},", field_pattern).unwrap();
+        }
+        record += "            )| Record {\n";
+    } else {
+        writeln!(record, "{}", closure_head).unwrap();
+    }
+    for name in names.iter() {
+        writeln!(record, "                {},", name).unwrap();
+    }
+    record += "            },\n";
+    record += "        )(input)\n";
+    record += "    }\n";
+    record += "}\n";
+    record
+}
+
+fn split_records(example: &str, structure: Structure) -> Vec<&str> {
+    match structure {
+        Structure::Blocks => example
+            .split("\n\n")
+            .map(|block| block.trim_matches('\n'))
+            .filter(|block| !block.is_empty())
+            .collect(),
+        _ => example.lines().filter(|line| !line.is_empty()).collect(),
+    }
+}
+
+/// Renders a module whose `Record` parser is inferred from the example, reusing
+/// everything from `pub fn part_one` onwards from the given line template.
+/// Blocks are iterated with `iterate_blocks` instead of `iterate_all`.
+pub fn render_module(example: &str, structure: Structure, line_template: &str) -> String {
+    let example = normalize(example);
+    let tokens = infer_tokens(&split_records(&example, structure));
+
+    let body_start = line_template
+        .find("pub fn part_one")
+        .unwrap_or(line_template.len());
+    format!(
+        "{}\n{}\n{}",
+        render_imports(&tokens, structure),
+        render_record(&tokens, structure),
+        line_template[body_start..].replace("iterate_all", iterator_name(structure))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code::helpers::parsing::{text_i32, text_u32, VerboseParsingError};
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{
+            alpha1, alphanumeric1, anychar, line_ending, not_line_ending, space1,
+        },
+        combinator::all_consuming,
+        multi::separated_list1,
+        IResult, Parser,
+    };
+
+    const CALORIES: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+    const STRATEGY: &str = "A Y\nB X\nC Z";
+    const ASSIGNMENTS: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9";
+    const CARGO: &str =
+        "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3";
+    const TERMINAL: &str = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d";
+    const MONKEYS: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3";
+    const SENSORS: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
+        Sensor at x=9, y=16: closest beacon is at x=10, y=16";
+    const BLUEPRINTS: &str =
+        "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+        Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.";
+
+    type TestResult<'a> = IResult<&'a [u8], (), VerboseParsingError<'a>>;
+
+    fn skip<'a, O>(result: IResult<&'a [u8], O, VerboseParsingError<'a>>) -> TestResult<'a> {
+        result.map(|(rest, _)| (rest, ()))
+    }
+
+    /// Reads back a string literal written with `{:?}`.
+    fn unescape(literal: &str) -> String {
+        let mut text = String::new();
+        let mut chars = literal
+            .strip_prefix('"')
+            .and_then(|literal| literal.strip_suffix('"'))
+            .unwrap_or_else(|| panic!("Not a string literal: {}", literal))
+            .chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => text.push('\n'),
+                Some('r') => text.push('\r'),
+                Some('t') => text.push('\t'),
+                Some('0') => text.push('\0'),
+                Some('u') => {
+                    let code: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                    let code = u32::from_str_radix(code.trim_start_matches('{'), 16).unwrap();
+                    text.push(char::from_u32(code).unwrap());
+                }
+                Some(other) => text.push(other),
+                None => panic!("Unterminated escape in {}", literal),
+            }
+        }
+        text
+    }
+
+    /// Splits the arguments of a call on the commas that aren't nested in
+    /// parentheses or string literals.
+    fn split_arguments(arguments: &str) -> Vec<&str> {
+        let mut parts = vec![];
+        let (mut depth, mut in_string, mut escaped, mut start) = (0, false, false, 0);
+        for (i, c) in arguments.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_string => escaped = true,
+                '"' => in_string = !in_string,
+                '(' if !in_string => depth += 1,
+                ')' if !in_string => depth -= 1,
+                ',' if !in_string && depth == 0 => {
+                    parts.push(arguments[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(arguments[start..].trim());
+        parts
+    }
+
+    /// Runs one parser expression exactly as it is written in a generated module.
+    fn run_parser<'a>(expr: &str, input: &'a [u8]) -> TestResult<'a> {
+        if let Some(literal) = expr
+            .strip_prefix("tag(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            return skip(tag(unescape(literal).as_str())(input));
+        }
+        if let Some(arguments) = expr
+            .strip_prefix("separated_list1(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let [separator, element] = split_arguments(arguments)[..] else {
+                panic!("Unexpected arguments in {}", expr);
+            };
+            return skip(separated_list1(
+                |input| run_parser(separator, input),
+                |input| run_parser(element, input),
+            )(input));
+        }
+        match expr {
+            "line_ending" => skip(line_ending(input)),
+            "space1" => skip(space1(input)),
+            "anychar" => skip(anychar(input)),
+            "text_u32()" => skip(text_u32().parse(input)),
+            "text_i32()" => skip(text_i32().parse(input)),
+            "text(alpha1)" => skip(alpha1(input)),
+            "text(alphanumeric1)" => skip(alphanumeric1(input)),
+            "text(not_line_ending)" => skip(not_line_ending(input)),
+            _ => panic!("Unexpected parser in generated module: {}", expr),
+        }
+    }
+
+    /// The parsers of the `tuple` in a generated `Record` impl, with nested
+    /// tuples flattened, in the order they run.
+    fn record_parsers(module: &str) -> Vec<String> {
+        let record_impl = &module[module.find("impl Aoc").expect("No Record impl")..];
+        let mut parsers = vec![];
+        let mut depth = 0;
+        for line in record_impl.lines().map(str::trim) {
+            if line.ends_with("tuple((") {
+                depth += 1;
+            } else if line.starts_with("))") {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            } else if depth > 0 {
+                parsers.push(line.trim_end_matches(',').to_string());
+            }
+        }
+        parsers
+    }
+
+    /// Checks that the generated module imports every parser it uses, and that
+    /// its `Record` parser accepts each record of the example.
+    fn inferred_parser_accepts_example(example: &str) -> Vec<Token> {
+        let structure = detect_structure(example);
+        assert_ne!(structure, Structure::Grid, "{:?} is a grid", example);
+        let module = generated_module(example);
+        let parsers = record_parsers(&module);
+        let imports = &module[..module.find("\n\n").unwrap()];
+        let uses = |name: &str| {
+            parsers.iter().any(|parser| {
+                parser
+                    .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .any(|word| word == name)
+            })
+        };
+        for name in [
+            "tag",
+            "line_ending",
+            "space1",
+            "anychar",
+            "text_u32",
+            "text_i32",
+            "alpha1",
+            "alphanumeric1",
+            "not_line_ending",
+            "separated_list1",
+        ] {
+            assert!(
+                !uses(name) || imports.contains(name),
+                "{} is not imported",
+                name
+            );
+        }
+        assert_eq!(uses("text"), module.contains("fn text<'a>("));
+
+        let example = normalize(example);
+        let records = split_records(&example, structure);
+        for record in &records {
+            let parsed = all_consuming(|mut input| {
+                for parser in &parsers {
+                    (input, _) = run_parser(parser, input)?;
+                }
+                Ok((input, ()))
+            })(record.as_bytes());
+            assert!(parsed.is_ok(), "{:?} rejects {:?}", parsers, record);
+        }
+        infer_tokens(&records)
+    }
+
+    fn generated_module(example: &str) -> String {
+        render_module(
+            example,
+            detect_structure(example),
+            include_str!("../../templates/line.tpl"),
+        )
+    }
+
+    #[test]
+    fn test_detect_structure() {
+        assert_eq!(detect_structure("30373\n25512\n65332\n"), Structure::Grid);
+        assert_eq!(detect_structure("Sabqponm\nabcryxxl\n"), Structure::Grid);
+        assert_eq!(
+            detect_structure("....#..\n..###.#\n#...#.#\n"),
+            Structure::Grid
+        );
+        assert_eq!(detect_structure(ASSIGNMENTS), Structure::Lines);
+        assert_eq!(
+            detect_structure("move 1 from 2 to 1\nmove 3 from 1 to 3\n"),
+            Structure::Lines
+        );
+        assert_eq!(detect_structure("1000\n2000\n\n4000\n"), Structure::Blocks);
+    }
+
+    #[test]
+    fn test_lex_signs() {
+        assert_eq!(
+            lex("x=-2, 4-6"),
+            vec![
+                Lexeme::Word("x".to_string()),
+                Lexeme::Punct('='),
+                Lexeme::Number { signed: true },
+                Lexeme::Punct(','),
+                Lexeme::Space(" ".to_string()),
+                Lexeme::Number { signed: false },
+                Lexeme::Punct('-'),
+                Lexeme::Number { signed: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_field_names() {
+        let tokens = infer_tokens(&["move 1 from 2 to 3"]);
+        assert_eq!(field_names(&tokens), vec!["move_value", "from", "to"]);
+        let tokens = infer_tokens(&["Sensor at x=2, y=18: closest beacon is at x=-2, y=15"]);
+        assert_eq!(field_names(&tokens), vec!["x", "y", "x_2", "y_2"]);
+        let tokens = infer_tokens(&["Starting items: 1, 2", "Starting items: 3"]);
+        assert_eq!(field_names(&tokens), vec!["items"]);
+    }
+
+    #[test]
+    fn test_infer_tokens_merges_signs() {
+        let tokens = infer_tokens(&["x=1", "x=-1"]);
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Literal("x=".to_string()),
+                Token::Number { signed: true }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_varying_records() {
+        assert_eq!(
+            inferred_parser_accepts_example(CALORIES),
+            vec![Token::List {
+                signed: false,
+                separator: "\n".to_string()
+            }]
+        );
+        assert_eq!(
+            inferred_parser_accepts_example(STRATEGY),
+            vec![
+                Token::Capture(Capture::Char),
+                Token::Literal(" ".to_string()),
+                Token::Capture(Capture::Char)
+            ]
+        );
+        assert_eq!(
+            inferred_parser_accepts_example(TERMINAL),
+            vec![Token::Capture(Capture::Line)]
+        );
+        let monkey = inferred_parser_accepts_example(MONKEYS);
+        assert_eq!(monkey.iter().filter(|token| token.is_field()).count(), 7);
+    }
+
+    #[test]
+    fn test_inferred_parsers_accept_their_examples() {
+        for example in [
+            CALORIES,
+            STRATEGY,
+            ASSIGNMENTS,
+            CARGO,
+            TERMINAL,
+            MONKEYS,
+            SENSORS,
+            BLUEPRINTS,
+            include_str!("../../examples/03.txt"),
+            include_str!("../../examples/05.txt"),
+            include_str!("../../examples/19.txt"),
+            include_str!("../../examples/20.txt"),
+        ] {
+            inferred_parser_accepts_example(example);
+            for line in generated_module(example).lines() {
+                assert!(line.len() <= MAX_WIDTH, "Line is too long: {}", line);
+            }
+        }
+    }
+}
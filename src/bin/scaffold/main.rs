@@ -0,0 +1,332 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    ffi::OsStr,
+    fs::{self, File, OpenOptions},
+    io::Write,
+    process,
+};
+
+mod infer;
+
+const TEMPLATES_DIR: &str = "src/templates";
+const DEFAULT_TEMPLATE: &str = "blank";
+const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+struct Args {
+    day: u8,
+    template: Option<String>,
+    answer_one: String,
+    answer_two: String,
+    force: bool,
+    dry_run: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let answer: Option<String> = args.opt_value_from_str(["-a", "--answer"])?;
+    let answer = answer.unwrap_or_else(|| DEFAULT_ANSWER_TYPE.to_string());
+    Ok(Args {
+        template: args.opt_value_from_str(["-t", "--template"])?,
+        answer_one: args
+            .opt_value_from_str("--answer-one")?
+            .unwrap_or_else(|| answer.clone()),
+        answer_two: args.opt_value_from_str("--answer-two")?.unwrap_or(answer),
+        force: args.contains(["-f", "--force"]),
+        dry_run: args.contains("--dry-run"),
+        day: args.free_from_str()?,
+    })
+}
+
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension() == Some(OsStr::new("tpl")))
+                .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+fn read_template(name: &str) -> Result<String, std::io::Error> {
+    fs::read_to_string(format!("{}/{}.tpl", TEMPLATES_DIR, name))
+}
+
+/// Picks the module template, inferring a parser from an already pasted example
+/// when no template was requested explicitly.
+fn resolve_template(template: &Option<String>, example_path: &str) -> (String, String) {
+    let example = fs::read_to_string(example_path).unwrap_or_default();
+    let (template_name, template) = match template {
+        Some(name) => (name.clone(), read_template(name)),
+        None if !example.trim().is_empty() => {
+            let structure = infer::detect_structure(&example);
+            println!(
+                "Inferred {} structure from example file \"{}\"",
+                structure, example_path
+            );
+            match structure {
+                infer::Structure::Grid => ("grid".to_string(), read_template("grid")),
+                _ => (
+                    "line".to_string(),
+                    read_template("line")
+                        .map(|line| infer::render_module(&example, structure, &line)),
+                ),
+            }
+        }
+        None => (
+            DEFAULT_TEMPLATE.to_string(),
+            read_template(DEFAULT_TEMPLATE),
+        ),
+    };
+    match template {
+        Ok(template) => (template_name, template),
+        Err(e) => {
+            eprintln!("Failed to read template \"{}\": {}", template_name, e);
+            eprintln!("Available templates: {}", available_templates().join(", "));
+            process::exit(1);
+        }
+    }
+}
+
+/// Fills in the `{{DAY}}`, `{{ANSWER_ONE}}` and `{{ANSWER_TWO}}` placeholders of a
+/// template in a single pass. Anything else, including other `{{...}}`, is kept as is.
+fn render_template(template: &str, day: u8, args: &Args) -> String {
+    let day = day.to_string();
+    let placeholders = [
+        ("{{DAY}}", day.as_str()),
+        ("{{ANSWER_ONE}}", args.answer_one.as_str()),
+        ("{{ANSWER_TWO}}", args.answer_two.as_str()),
+    ];
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find("{{") {
+        rendered.push_str(&rest[..open]);
+        rest = &rest[open..];
+        match placeholders
+            .iter()
+            .find(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some((placeholder, value)) => {
+                rendered.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                rendered.push_str("{{");
+                rest = &rest[2..];
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FileState {
+    Missing,
+    Empty,
+    NonEmpty,
+}
+
+impl FileState {
+    fn of(path: &str) -> FileState {
+        match fs::metadata(path) {
+            Err(_) => FileState::Missing,
+            Ok(metadata) if metadata.len() == 0 => FileState::Empty,
+            Ok(_) => FileState::NonEmpty,
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            FileState::Missing => "missing",
+            FileState::Empty => "exists (empty)",
+            FileState::NonEmpty => "exists",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Create,
+    Overwrite,
+    Keep,
+}
+
+struct PlannedFile<'a> {
+    kind: &'static str,
+    path: &'a str,
+    state: FileState,
+    action: Action,
+}
+
+impl<'a> PlannedFile<'a> {
+    /// Modules may be regenerated with `--force`, but inputs and examples
+    /// are only ever created when missing so pasted data can't be lost.
+    fn plan(kind: &'static str, path: &'a str, overwritable: bool) -> PlannedFile<'a> {
+        let state = FileState::of(path);
+        let action = match state {
+            FileState::Missing => Action::Create,
+            _ if overwritable => Action::Overwrite,
+            _ => Action::Keep,
+        };
+        PlannedFile {
+            kind,
+            path,
+            state,
+            action,
+        }
+    }
+
+    fn print(&self) {
+        let action = match self.action {
+            Action::Create => "creating",
+            Action::Overwrite => "overwriting",
+            Action::Keep => "keeping",
+        };
+        println!(
+            "{} \"{}\" {} → {}",
+            self.kind,
+            self.path,
+            self.state.describe(),
+            action
+        );
+    }
+}
+
+fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+fn overwrite_file(path: &str) -> Result<File, std::io::Error> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+}
+
+fn open_planned_file(planned: &PlannedFile) -> Result<File, std::io::Error> {
+    match planned.action {
+        Action::Overwrite => overwrite_file(planned.path),
+        _ => safe_create_file(planned.path),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(_) => {
+            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
+            process::exit(1);
+        }
+    };
+    let day = args.day;
+
+    let day_padded = format!("{:02}", day);
+
+    let input_path = format!("src/inputs/{}.txt", day_padded);
+    let example_path = format!("src/examples/{}.txt", day_padded);
+    let module_path = format!("src/bin/{}.rs", day_padded);
+
+    let module = PlannedFile::plan("module", &module_path, args.force);
+    let input = PlannedFile::plan("input", &input_path, false);
+    let example = PlannedFile::plan("example", &example_path, false);
+    for planned in [&module, &input, &example] {
+        planned.print();
+    }
+
+    // Resolved up front so that a mistyped `--template` is reported even on a dry run.
+    let (template_name, template) = resolve_template(&args.template, &example_path);
+
+    if module.action == Action::Keep {
+        println!("Use `--force` to regenerate the existing module file.");
+    }
+    if args.dry_run {
+        println!("Dry run, no files were changed.");
+        return;
+    }
+
+    if module.action != Action::Keep {
+        let mut file = match open_planned_file(&module) {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("Failed to create module file: {}", e);
+                process::exit(1);
+            }
+        };
+
+        match file.write_all(render_template(&template, day, &args).as_bytes()) {
+            Ok(_) => {
+                println!(
+                    "Wrote module file \"{}\" from template \"{}\"",
+                    module_path, template_name
+                );
+            }
+            Err(e) => {
+                eprintln!("Failed to write module contents: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    for planned in [&input, &example] {
+        if planned.action != Action::Create {
+            continue;
+        }
+        match open_planned_file(planned) {
+            Ok(_) => {
+                println!("Created empty {} file \"{}\"", planned.kind, planned.path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create {} file: {}", planned.kind, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    println!("---");
+    println!("🎄 Type `cargo solve {}` to run your solution.", day_padded);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_width_records_are_not_grids() {
+        let (template_name, module) = resolve_template(&None, "src/examples/04.txt");
+        assert_eq!(template_name, "line");
+        assert!(module.contains("impl AocLineParsable for Record"));
+        assert!(module.contains("tag(\"-\")"));
+    }
+
+    #[test]
+    fn test_render_template_replaces_only_placeholders() {
+        let args = Args {
+            day: 7,
+            template: None,
+            answer_one: "u64".to_string(),
+            answer_two: "String".to_string(),
+            force: false,
+            dry_run: false,
+        };
+        let template = "const DAY_COUNT: u8 = {{DAY}};\n\
+            fn part_one() -> Option<{{ANSWER_ONE}}> {}\n\
+            fn part_two() -> Option<{{ANSWER_TWO}}> {}\n\
+            let ANSWER_ONE = format!(\"{{DAYS}}\");\n";
+        assert_eq!(
+            render_template(template, 7, &args),
+            "const DAY_COUNT: u8 = 7;\n\
+            fn part_one() -> Option<u64> {}\n\
+            fn part_two() -> Option<String> {}\n\
+            let ANSWER_ONE = format!(\"{{DAYS}}\");\n"
+        );
+    }
+}
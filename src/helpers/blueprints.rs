@@ -0,0 +1,127 @@
+use std::fmt::{self, Write};
+
+use nom::{
+    branch::alt, bytes::complete::tag, combinator::value, multi::separated_list1,
+    sequence::separated_pair, Parser,
+};
+
+use super::parsing::{
+    text_u16, AocFormattable, AocLineFormattable, AocLineParsable, AocParsable, ParsingResult,
+};
+
+/// The materials in the order their amounts are stored in a `RobotCost`.
+pub const MATERIALS: [&str; 4] = ["ore", "clay", "obsidian", "geode"];
+
+/// What building one robot takes, like `3 ore and 14 clay`, as an amount of each
+/// of the `MATERIALS`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RobotCost(pub [u16; 4]);
+
+impl AocParsable for RobotCost {
+    fn parse_from_string(input: &[u8]) -> ParsingResult<'_, RobotCost> {
+        let material = alt((
+            value(0, tag("ore")),
+            value(1, tag("clay")),
+            value(2, tag("obsidian")),
+            value(3, tag("geode")),
+        ));
+        separated_list1(tag(" and "), separated_pair(text_u16(), tag(" "), material))
+            .map(|amounts| {
+                let mut cost = RobotCost::default();
+                for (amount, material) in amounts {
+                    cost.0[material] += amount;
+                }
+                cost
+            })
+            .parse(input)
+    }
+}
+
+/// Only the materials that are needed are written, so a robot that costs nothing
+/// at all is written as `0 ore`.
+impl AocFormattable for RobotCost {
+    fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+        let mut needed = MATERIALS
+            .iter()
+            .zip(self.0)
+            .filter(|(_, amount)| *amount > 0)
+            .peekable();
+        if needed.peek().is_none() {
+            return out.write_str("0 ore");
+        }
+        for (i, (material, amount)) in needed.enumerate() {
+            if i > 0 {
+                out.write_str(" and ")?;
+            }
+            write!(out, "{} {}", amount, material)?;
+        }
+        Ok(())
+    }
+}
+
+/// One line of robot factory blueprints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AocLineParsable, AocLineFormattable)]
+#[aoc(
+    pattern = "Blueprint {number}: Each ore robot costs {ore_robot}. Each clay robot costs {clay_robot}. Each obsidian robot costs {obsidian_robot}. Each geode robot costs {geode_robot}."
+)]
+pub struct BlueprintCosts {
+    pub number: u8,
+    pub ore_robot: RobotCost,
+    pub clay_robot: RobotCost,
+    pub obsidian_robot: RobotCost,
+    pub geode_robot: RobotCost,
+}
+
+impl BlueprintCosts {
+    /// The cost of each robot, in the same order as `MATERIALS`.
+    pub fn robot_costs(&self) -> [RobotCost; 4] {
+        [
+            self.ore_robot,
+            self.clay_robot,
+            self.obsidian_robot,
+            self.geode_robot,
+        ]
+    }
+
+    /// The most of each material that any one robot needs.
+    pub fn max_costs(&self) -> [u16; 4] {
+        let mut max = [0; 4];
+        for cost in self.robot_costs() {
+            for (max, amount) in max.iter_mut().zip(cost.0) {
+                *max = (*max).max(amount);
+            }
+        }
+        max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::{assert_round_trips, parse_all};
+
+    const BLUEPRINTS: &str = "Blueprint 1: Each ore robot costs 4 ore. \
+        Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. \
+        Each geode robot costs 2 ore and 7 obsidian.\n\
+        Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. \
+        Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.\n";
+
+    #[test]
+    fn test_parse_blueprints() {
+        let blueprints: Vec<BlueprintCosts> = parse_all(BLUEPRINTS.as_bytes()).unwrap();
+        assert_eq!(blueprints[0].obsidian_robot, RobotCost([3, 14, 0, 0]));
+        assert_eq!(blueprints[1].max_costs(), [3, 8, 12, 0]);
+        assert_eq!(blueprints.to_puzzle_string(), BLUEPRINTS);
+    }
+
+    #[test]
+    fn test_blueprint_round_trip() {
+        assert_round_trips((0..=255).map(|i| BlueprintCosts {
+            number: i,
+            ore_robot: RobotCost([i as u16, 0, 0, 0]),
+            clay_robot: RobotCost([0, 0, 0, 0]),
+            obsidian_robot: RobotCost([3, i as u16 * 100, 0, 0]),
+            geode_robot: RobotCost([2, 0, i as u16 % 5, 1]),
+        }));
+    }
+}
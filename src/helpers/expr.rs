@@ -0,0 +1,450 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+};
+
+use nom::{
+    branch::alt,
+    character::complete::{alpha1, char, one_of, space0},
+    combinator::map,
+    multi::many0,
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+};
+use num::{BigInt, BigRational, One, Zero};
+use petgraph::{
+    algo::toposort,
+    graphmap::DiGraphMap,
+    visit::{Dfs, Walker},
+};
+
+use super::{
+    parsing::{line_ending_or_eof, parse_complete, text_i64, AocParseError, ParsingResult},
+    symbols::{symbol, Symbol, SymbolTable},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl BinaryOp {
+    fn from_char(c: char) -> BinaryOp {
+        match c {
+            '+' => BinaryOp::Add,
+            '-' => BinaryOp::Sub,
+            '*' => BinaryOp::Mul,
+            '/' => BinaryOp::Div,
+            _ => unreachable!("Not an operator: {}", c),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Number(i64),
+    Ref(Symbol),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Every name this expression refers to, in order of appearance.
+    pub fn references(&self) -> Vec<Symbol> {
+        let mut references = vec![];
+        let mut pending = vec![self];
+        while let Some(expr) = pending.pop() {
+            match expr {
+                Expr::Number(_) => {}
+                Expr::Ref(name) => references.push(*name),
+                Expr::Binary(_, left, right) => {
+                    pending.push(right);
+                    pending.push(left);
+                }
+            }
+        }
+        references
+    }
+}
+
+/// Parses `+ - * /` over integers, names and parentheses, with the usual
+/// precedence and left associativity. Names are interned into `table`.
+pub fn expression<'a, 't>(
+    table: &'t RefCell<SymbolTable>,
+) -> impl FnMut(&'a [u8]) -> ParsingResult<'a, Expr> + 't {
+    move |input| parse_sum(table, input)
+}
+
+/// Parses a `name: expression` line, without the line ending.
+pub fn definition<'a, 't>(
+    table: &'t RefCell<SymbolTable>,
+) -> impl FnMut(&'a [u8]) -> ParsingResult<'a, (Symbol, Expr)> + 't {
+    move |input| {
+        separated_pair(
+            symbol(table, alpha1),
+            pair(char(':'), space0),
+            expression(table),
+        )(input)
+    }
+}
+
+fn parse_sum<'a>(table: &RefCell<SymbolTable>, input: &'a [u8]) -> ParsingResult<'a, Expr> {
+    let (input, first) = parse_product(table, input)?;
+    let (input, rest) = many0(pair(delimited(space0, one_of("+-"), space0), |input| {
+        parse_product(table, input)
+    }))(input)?;
+    Ok((input, fold_operations(first, rest)))
+}
+
+fn parse_product<'a>(table: &RefCell<SymbolTable>, input: &'a [u8]) -> ParsingResult<'a, Expr> {
+    let (input, first) = parse_operand(table, input)?;
+    let (input, rest) = many0(pair(delimited(space0, one_of("*/"), space0), |input| {
+        parse_operand(table, input)
+    }))(input)?;
+    Ok((input, fold_operations(first, rest)))
+}
+
+fn parse_operand<'a>(table: &RefCell<SymbolTable>, input: &'a [u8]) -> ParsingResult<'a, Expr> {
+    alt((
+        map(text_i64(), Expr::Number),
+        map(symbol(table, alpha1), Expr::Ref),
+        delimited(
+            terminated(char('('), space0),
+            |input| parse_sum(table, input),
+            preceded(space0, char(')')),
+        ),
+    ))(input)
+}
+
+fn fold_operations(first: Expr, rest: Vec<(char, Expr)>) -> Expr {
+    rest.into_iter().fold(first, |left, (op, right)| {
+        Expr::Binary(BinaryOp::from_char(op), Box::new(left), Box::new(right))
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprError {
+    Undefined(Symbol),
+    /// The symbol is part of a reference cycle.
+    Cycle(Symbol),
+    DivisionByZero,
+    /// The unknown was multiplied by itself, or ended up in a divisor.
+    NonLinear,
+    /// The unknown cancels out, so there are no solutions or infinitely many.
+    NoUniqueSolution,
+}
+
+impl ExprError {
+    /// Shows symbols by the names they were interned from, rather than by id.
+    pub fn with_names<'e>(&'e self, table: &'e SymbolTable) -> NamedExprError<'e> {
+        NamedExprError { error: self, table }
+    }
+
+    fn write_message(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        name: impl Fn(Symbol) -> String,
+    ) -> fmt::Result {
+        match self {
+            ExprError::Undefined(symbol) => write!(f, "symbol {} is not defined", name(*symbol)),
+            ExprError::Cycle(symbol) => write!(f, "symbol {} depends on itself", name(*symbol)),
+            ExprError::DivisionByZero => write!(f, "division by zero"),
+            ExprError::NonLinear => write!(f, "the unknown does not appear linearly"),
+            ExprError::NoUniqueSolution => write!(f, "the equation has no unique solution"),
+        }
+    }
+}
+
+/// Without the symbol table only the ids are known; see `ExprError::with_names`.
+impl Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_message(f, |symbol| symbol.0.to_string())
+    }
+}
+
+/// An `ExprError` that displays symbol names. Created by `ExprError::with_names`.
+pub struct NamedExprError<'e> {
+    error: &'e ExprError,
+    table: &'e SymbolTable,
+}
+
+impl Display for NamedExprError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error
+            .write_message(f, |symbol| format!("`{}`", self.table.resolve(symbol)))
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// `coefficient * unknown + constant`. Without an unknown this is just a constant.
+/// Intermediate values easily outgrow 64 bits, so these are arbitrary precision.
+#[derive(Clone, Debug)]
+struct Linear {
+    coefficient: BigRational,
+    constant: BigRational,
+}
+
+impl Linear {
+    fn constant(value: BigRational) -> Self {
+        Linear {
+            coefficient: BigRational::zero(),
+            constant: value,
+        }
+    }
+
+    fn unknown() -> Self {
+        Linear {
+            coefficient: BigRational::one(),
+            constant: BigRational::zero(),
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.coefficient.is_zero()
+    }
+
+    fn scale(self, factor: &BigRational) -> Self {
+        Linear {
+            coefficient: self.coefficient * factor,
+            constant: self.constant * factor,
+        }
+    }
+
+    fn apply(self, op: BinaryOp, other: Linear) -> Result<Linear, ExprError> {
+        match op {
+            BinaryOp::Add => Ok(Linear {
+                coefficient: self.coefficient + other.coefficient,
+                constant: self.constant + other.constant,
+            }),
+            BinaryOp::Sub => Ok(Linear {
+                coefficient: self.coefficient - other.coefficient,
+                constant: self.constant - other.constant,
+            }),
+            BinaryOp::Mul if self.is_constant() => Ok(other.scale(&self.constant)),
+            BinaryOp::Mul if other.is_constant() => Ok(self.scale(&other.constant)),
+            BinaryOp::Div if other.is_constant() => {
+                if other.constant.is_zero() {
+                    Err(ExprError::DivisionByZero)
+                } else {
+                    Ok(self.scale(&other.constant.recip()))
+                }
+            }
+            _ => Err(ExprError::NonLinear),
+        }
+    }
+}
+
+/// A set of named expressions that can refer to each other.
+#[derive(Clone, Debug, Default)]
+pub struct Definitions {
+    exprs: HashMap<Symbol, Expr>,
+}
+
+impl FromIterator<(Symbol, Expr)> for Definitions {
+    fn from_iter<I: IntoIterator<Item = (Symbol, Expr)>>(iter: I) -> Self {
+        Definitions {
+            exprs: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Definitions {
+    /// Parses one `name: expression` definition per line.
+    pub fn parse(input: &[u8], table: &RefCell<SymbolTable>) -> Result<Self, AocParseError> {
+        parse_complete(
+            input,
+            many0(terminated(definition(table), line_ending_or_eof())),
+        )
+        .map(Definitions::from_iter)
+    }
+
+    pub fn get(&self, name: Symbol) -> Option<&Expr> {
+        self.exprs.get(&name)
+    }
+
+    pub fn insert(&mut self, name: Symbol, expr: Expr) -> Option<Expr> {
+        self.exprs.insert(name, expr)
+    }
+
+    pub fn remove(&mut self, name: Symbol) -> Option<Expr> {
+        self.exprs.remove(&name)
+    }
+
+    /// Has an edge from every definition to each name it refers to.
+    pub fn dependency_graph(&self) -> DiGraphMap<Symbol, ()> {
+        let mut graph = DiGraphMap::new();
+        for (name, expr) in &self.exprs {
+            graph.add_node(*name);
+            for reference in expr.references() {
+                graph.add_edge(*name, reference, ());
+            }
+        }
+        graph
+    }
+
+    /// The definitions `target` depends on, each listed after everything it
+    /// refers to and ending with `target` itself.
+    pub fn evaluation_order(&self, target: Symbol) -> Result<Vec<Symbol>, ExprError> {
+        let graph = self.dependency_graph();
+        if !graph.contains_node(target) {
+            return Err(ExprError::Undefined(target));
+        }
+        let reachable: HashSet<Symbol> = Dfs::new(&graph, target).iter(&graph).collect();
+        let mut needed: DiGraphMap<Symbol, ()> = graph
+            .all_edges()
+            .filter(|(from, _, _)| reachable.contains(from))
+            .map(|(from, to, _)| (from, to))
+            .collect();
+        needed.add_node(target);
+        let mut order =
+            toposort(&needed, None).map_err(|cycle| ExprError::Cycle(cycle.node_id()))?;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Evaluates `target` exactly. Only the definitions it depends on need to be valid.
+    pub fn evaluate(&self, target: Symbol) -> Result<BigRational, ExprError> {
+        Ok(self.evaluate_linear(target, None)?.constant)
+    }
+
+    /// Finds the value of `unknown` that makes `left` and `right` equal, ignoring
+    /// any definition `unknown` already has. Both sides must be linear in it.
+    pub fn solve(
+        &self,
+        unknown: Symbol,
+        left: Symbol,
+        right: Symbol,
+    ) -> Result<BigRational, ExprError> {
+        let left = self.evaluate_linear(left, Some(unknown))?;
+        let right = self.evaluate_linear(right, Some(unknown))?;
+        let coefficient = left.coefficient - right.coefficient;
+        if coefficient.is_zero() {
+            return Err(ExprError::NoUniqueSolution);
+        }
+        Ok((right.constant - left.constant) / coefficient)
+    }
+
+    fn evaluate_linear(
+        &self,
+        target: Symbol,
+        unknown: Option<Symbol>,
+    ) -> Result<Linear, ExprError> {
+        let mut values: HashMap<Symbol, Linear> = HashMap::new();
+        if let Some(unknown) = unknown {
+            values.insert(unknown, Linear::unknown());
+        }
+        if unknown == Some(target) {
+            return Ok(Linear::unknown());
+        }
+        for name in self.evaluation_order(target)? {
+            if values.contains_key(&name) {
+                continue;
+            }
+            let expr = self.get(name).ok_or(ExprError::Undefined(name))?;
+            let value = Self::evaluate_expr(expr, &values)?;
+            values.insert(name, value);
+        }
+        Ok(values.remove(&target).unwrap())
+    }
+
+    fn evaluate_expr(expr: &Expr, values: &HashMap<Symbol, Linear>) -> Result<Linear, ExprError> {
+        match expr {
+            Expr::Number(value) => Ok(Linear::constant(BigRational::from_integer(BigInt::from(
+                *value,
+            )))),
+            Expr::Ref(name) => values.get(name).cloned().ok_or(ExprError::Undefined(*name)),
+            Expr::Binary(op, left, right) => {
+                let left = Self::evaluate_expr(left, values)?;
+                let right = Self::evaluate_expr(right, values)?;
+                left.apply(*op, right)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONKEYS: &str = "root: pppw + sjmn\ndbpl: 5\ncczh: sllz + lgvd\nzczc: 2\n\
+        ptdq: humn - dvpt\ndvpt: 3\nlfqf: 4\nhumn: 5\nljgn: 2\nsjmn: drzm * dbpl\n\
+        sllz: 4\npppw: cczh / lfqf\nlgvd: ljgn * ptdq\ndrzm: hmdt - zczc\nhmdt: 32\n";
+
+    fn ratio(numerator: i64, denominator: i64) -> BigRational {
+        BigRational::new(numerator.into(), denominator.into())
+    }
+
+    fn parse(text: &str) -> (Definitions, SymbolTable) {
+        let table = RefCell::new(SymbolTable::new());
+        let definitions = Definitions::parse(text.as_bytes(), &table).unwrap();
+        (definitions, table.into_inner())
+    }
+
+    #[test]
+    fn test_precedence() {
+        let (definitions, table) = parse("a: 2 + 3 * (4 - 1) - 10 / 4\nb: a * a");
+        let a = table.get("a").unwrap();
+        let b = table.get("b").unwrap();
+        assert_eq!(definitions.evaluate(a), Ok(ratio(17, 2)));
+        assert_eq!(definitions.evaluate(b), Ok(ratio(289, 4)));
+    }
+
+    #[test]
+    fn test_evaluate_and_solve() {
+        let (definitions, table) = parse(MONKEYS);
+        let name = |name: &str| table.get(name).unwrap();
+        assert_eq!(definitions.evaluate(name("root")), Ok(ratio(152, 1)));
+        assert_eq!(
+            definitions.solve(name("humn"), name("pppw"), name("sjmn")),
+            Ok(ratio(301, 1))
+        );
+    }
+
+    #[test]
+    fn test_intermediate_values_beyond_i64() {
+        let (definitions, table) = parse(
+            "big: 3000000000 * 4000000000\nhuge: big * big\nroot: huge / big / 4000000000\n\
+            left: (humn - big) * huge / 2\nright: huge * 5",
+        );
+        let name = |name: &str| table.get(name).unwrap();
+        assert_eq!(definitions.evaluate(name("root")), Ok(ratio(3000000000, 1)));
+        assert_eq!(
+            definitions.solve(name("humn"), name("left"), name("right")),
+            Ok("12000000000000000010".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let (definitions, table) =
+            parse("a: b + 1\nb: c * 2\nc: a\nd: 1 / (e - e)\ne: 4\nf: g\nh: 1 / e");
+        let name = |name: &str| table.get(name).unwrap();
+        assert!(matches!(
+            definitions.evaluate(name("a")),
+            Err(ExprError::Cycle(_))
+        ));
+        assert_eq!(
+            definitions.evaluate(name("d")),
+            Err(ExprError::DivisionByZero)
+        );
+        assert_eq!(
+            definitions.evaluate(name("f")),
+            Err(ExprError::Undefined(name("g")))
+        );
+        assert_eq!(
+            definitions
+                .evaluate(name("f"))
+                .unwrap_err()
+                .with_names(&table)
+                .to_string(),
+            "symbol `g` is not defined"
+        );
+        assert_eq!(
+            definitions.solve(name("e"), name("h"), name("e")),
+            Err(ExprError::NonLinear)
+        );
+    }
+}
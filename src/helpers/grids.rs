@@ -1,4 +1,6 @@
-use std::{fmt::{Debug, Write}, ops::{Deref, DerefMut, Add, Mul}};
+use std::{collections::HashMap, fmt::{Debug, Write}, ops::{Deref, DerefMut, Add, Mul, Index, IndexMut, RangeInclusive, Sub}};
+
+use strum_macros::FromRepr;
 
 pub trait ToGridChar {
     fn to_grid_char(&self) -> char;
@@ -63,6 +65,32 @@ impl<T, const S: usize> SquareGrid<T, S> {
     pub fn new_with(val: T) -> Self where T: Copy {
         Self::from_array([[val; S]; S])
     }
+
+    /// `(rows, cols)`, for building `LineIterator`s.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (S, S)
+    }
+
+    /// Returns `None` for points outside the grid.
+    pub fn get(&self, point: Point2) -> Option<&T> {
+        let (row, col) = point.within(self.dimensions())?;
+        Some(&self[row][col])
+    }
+
+    pub fn get_mut(&mut self, point: Point2) -> Option<&mut T> {
+        let (row, col) = point.within(self.dimensions())?;
+        Some(&mut self[row][col])
+    }
+
+    /// The up to four orthogonal neighbours of `point` that lie inside the grid.
+    pub fn neighbors4(&self, point: Point2) -> impl Iterator<Item = Point2> {
+        neighbors_within(point, self.dimensions(), &Direction::CARDINAL)
+    }
+
+    /// Like `neighbors4`, but including diagonal neighbours.
+    pub fn neighbors8(&self, point: Point2) -> impl Iterator<Item = Point2> {
+        neighbors_within(point, self.dimensions(), &Direction::ALL)
+    }
 }
 
 impl<T: Add + Copy, const S: usize> Add for SquareGrid<T, S> where <T as Add>::Output: Into<T> {
@@ -103,83 +131,595 @@ impl<T: ToGridChar, const S: usize> Debug for SquareGrid<T, S> {
     }
 }
 
-#[derive(Debug)]
-struct LineOrientation {
-    col_step: isize,
-    row_step: isize,
+/// A rectangular grid sized at runtime, stored row by row in one allocation.
+/// `grid[row]` is a slice of that row, so cells are read as `grid[row][col]`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new_with(width: usize, height: usize, val: T) -> Self where T: Clone {
+        Grid {
+            cells: vec![val; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Returns `None` if the rows aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// `(rows, cols)`, for building `LineIterator`s.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Returns `None` for points outside the grid.
+    pub fn get(&self, point: Point2) -> Option<&T> {
+        let (row, col) = point.within(self.dimensions())?;
+        Some(&self[row][col])
+    }
+
+    pub fn get_mut(&mut self, point: Point2) -> Option<&mut T> {
+        let (row, col) = point.within(self.dimensions())?;
+        Some(&mut self[row][col])
+    }
+
+    /// The up to four orthogonal neighbours of `point` that lie inside the grid.
+    pub fn neighbors4(&self, point: Point2) -> impl Iterator<Item = Point2> {
+        neighbors_within(point, self.dimensions(), &Direction::CARDINAL)
+    }
+
+    /// Like `neighbors4`, but including diagonal neighbours.
+    pub fn neighbors8(&self, point: Point2) -> impl Iterator<Item = Point2> {
+        neighbors_within(point, self.dimensions(), &Direction::ALL)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |row| &self[row])
+    }
+
+    /// Every cell in reading order.
+    pub fn cells(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter()
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.cells.iter_mut()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn into_rows(self) -> Vec<Vec<T>> {
+        let mut cells = self.cells.into_iter();
+        (0..self.height)
+            .map(|_| cells.by_ref().take(self.width).collect())
+            .collect()
+    }
+
+    fn zip_with(mut self, rhs: Self, f: impl Fn(T, T) -> T) -> Self where T: Copy {
+        assert_eq!(self.dimensions(), rhs.dimensions(), "Grid sizes differ");
+        for (cell, other) in self.cells.iter_mut().zip(rhs.cells) {
+            *cell = f(*cell, other);
+        }
+        self
+    }
 }
 
-const LINE_ORIENTATION_RIGHT_TO_LEFT: LineOrientation = LineOrientation {
-    col_step: -1,
-    row_step: 0,
-};
-const LINE_ORIENTATION_LEFT_TO_RIGHT: LineOrientation = LineOrientation {
-    col_step: 1,
-    row_step: 0,
-};
-const LINE_ORIENTATION_TOP_TO_BOTTOM: LineOrientation = LineOrientation {
-    col_step: 0,
-    row_step: 1,
-};
-const LINE_ORIENTATION_BOTTOM_TO_TOP: LineOrientation = LineOrientation {
-    col_step: 0,
-    row_step: -1,
-};
+impl<T> Index<usize> for Grid<T> {
+    type Output = [T];
 
-pub struct LineIterator<const S: usize> {
-    orientation: LineOrientation,
-    row: isize,
-    col: isize,
+    fn index(&self, row: usize) -> &Self::Output {
+        assert!(row < self.height, "Row {} is outside the grid", row);
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
 }
 
-impl<const S: usize> LineIterator<S> {
-    pub fn get_line_from_top<'a>(offset: usize) -> LineIterator<S> {
-        LineIterator {
-            orientation: LINE_ORIENTATION_TOP_TO_BOTTOM,
-            col: isize::try_from(offset).expect("Offset is too large"),
-            row: 0,
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, row: usize) -> &mut Self::Output {
+        assert!(row < self.height, "Row {} is outside the grid", row);
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+}
+
+impl<T: Add + Copy> Add for Grid<T> where <T as Add>::Output: Into<T> {
+    type Output = Grid<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| (a + b).into())
+    }
+}
+
+impl<T: Mul + Copy> Mul for Grid<T> where <T as Mul>::Output: Into<T> {
+    type Output = Grid<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| (a * b).into())
+    }
+}
+
+impl<T: ToGridChar> Debug for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                f.write_char(cell.to_grid_char())?;
+            }
+            f.write_char('\n')?;
         }
+        Ok(())
     }
+}
 
-    pub fn get_line_from_left(offset: usize) -> LineIterator<S> {
-        LineIterator {
-            orientation: LINE_ORIENTATION_LEFT_TO_RIGHT,
-            col: 0,
-            row: isize::try_from(offset).expect("Offset is too large"),
+impl<T, const S: usize> From<SquareGrid<T, S>> for Grid<T> {
+    fn from(grid: SquareGrid<T, S>) -> Self {
+        Grid {
+            cells: grid.0.into_iter().flatten().collect(),
+            width: S,
+            height: S,
         }
     }
+}
 
-    pub fn get_line_from_bottom(offset: usize) -> LineIterator<S> {
-        LineIterator {
-            orientation: LINE_ORIENTATION_BOTTOM_TO_TOP,
-            col: isize::try_from(offset).expect("Offset is too large"),
-            row: isize::try_from(S).expect("Grid size is too large") - 1,
+/// Fails with the original grid if it isn't `S` by `S`.
+impl<T, const S: usize> TryFrom<Grid<T>> for SquareGrid<T, S> {
+    type Error = Grid<T>;
+
+    fn try_from(grid: Grid<T>) -> Result<Self, Self::Error> {
+        if grid.dimensions() != (S, S) {
+            return Err(grid);
         }
+        let rows: Vec<[T; S]> = grid
+            .into_rows()
+            .into_iter()
+            .map(|row| row.try_into().ok().unwrap())
+            .collect();
+        Ok(SquareGrid::from_array(rows.try_into().ok().unwrap()))
+    }
+}
+
+/// A position with `x` growing to the right and `y` growing downwards, so that
+/// `y` is the row and `x` the column of a grid cell. Unlike `(row, col)` tuples,
+/// points can step off the edge of a grid and go negative.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub fn new(x: isize, y: isize) -> Self {
+        Point2 { x, y }
+    }
+
+    /// Returns `None` if the position doesn't fit in an `isize`.
+    pub fn from_row_col(row: usize, col: usize) -> Option<Self> {
+        Some(Point2 {
+            x: isize::try_from(col).ok()?,
+            y: isize::try_from(row).ok()?,
+        })
     }
 
-    pub fn get_line_from_right(offset: usize) -> LineIterator<S> {
+    /// Returns `None` for points above or left of the origin.
+    pub fn to_row_col(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+
+    /// The `(row, col)` of this point if it lies within a grid of `(rows, cols)`.
+    pub fn within(self, dimensions: (usize, usize)) -> Option<(usize, usize)> {
+        let (row, col) = self.to_row_col()?;
+        (row < dimensions.0 && col < dimensions.1).then_some((row, col))
+    }
+
+    pub fn checked_add(self, other: Point2) -> Option<Point2> {
+        Some(Point2 {
+            x: self.x.checked_add(other.x)?,
+            y: self.y.checked_add(other.y)?,
+        })
+    }
+
+    pub fn checked_sub(self, other: Point2) -> Option<Point2> {
+        Some(Point2 {
+            x: self.x.checked_sub(other.x)?,
+            y: self.y.checked_sub(other.y)?,
+        })
+    }
+
+    /// One step in `direction`, or `None` on overflow.
+    pub fn checked_step(self, direction: Direction) -> Option<Point2> {
+        self.checked_add(direction.offset())
+    }
+
+    pub fn step(self, direction: Direction) -> Point2 {
+        self + direction.offset()
+    }
+
+    pub fn manhattan_distance(self, other: Point2) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+/// The eight compass directions on screen, in clockwise order from `Up`.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromRepr)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The four directions that don't move diagonally, clockwise from `Up`.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::UpRight => Point2::new(1, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::DownRight => Point2::new(1, 1),
+            Direction::Down => Point2::new(0, 1),
+            Direction::DownLeft => Point2::new(-1, 1),
+            Direction::Left => Point2::new(-1, 0),
+            Direction::UpLeft => Point2::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as u8 % 2 == 1
+    }
+
+    fn turn(self, eighths: u8) -> Direction {
+        Direction::from_repr((self as u8 + eighths) % 8).unwrap()
+    }
+
+    /// A quarter turn clockwise.
+    pub fn rotate_right(self) -> Direction {
+        self.turn(2)
+    }
+
+    /// A quarter turn anticlockwise.
+    pub fn rotate_left(self) -> Direction {
+        self.turn(6)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.turn(4)
+    }
+}
+
+/// An unbounded grid that only stores the cells that have been set. Every other
+/// cell reads as the default value. Coordinates may be negative.
+#[derive(Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2, T>,
+    default: T,
+    bounds: Option<(Point2, Point2)>,
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    /// The value at `point`, or the default if it was never set.
+    pub fn get(&self, point: Point2) -> &T {
+        self.cells.get(&point).unwrap_or(&self.default)
+    }
+
+    pub fn contains(&self, point: Point2) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn insert(&mut self, point: Point2, value: T) -> Option<T> {
+        self.bounds = Some(Self::extend_bounds(self.bounds, point));
+        self.cells.insert(point, value)
+    }
+
+    /// Unsets `point`, shrinking the bounding box if it sat on the edge.
+    pub fn remove(&mut self, point: Point2) -> Option<T> {
+        let removed = self.cells.remove(&point)?;
+        if let Some((min, max)) = self.bounds {
+            if point.x == min.x || point.y == min.y || point.x == max.x || point.y == max.y {
+                self.bounds = Self::bounds_of(self.cells.keys());
+            }
+        }
+        Some(removed)
+    }
+
+    fn extend_bounds(bounds: Option<(Point2, Point2)>, point: Point2) -> (Point2, Point2) {
+        match bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                Point2::new(min.x.min(point.x), min.y.min(point.y)),
+                Point2::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+        }
+    }
+
+    fn bounds_of<'a>(points: impl Iterator<Item = &'a Point2>) -> Option<(Point2, Point2)> {
+        points.fold(None, |bounds, point| Some(Self::extend_bounds(bounds, *point)))
+    }
+
+    /// Empty while the grid is empty.
+    fn x_range(&self) -> RangeInclusive<isize> {
+        self.bounds.map_or(RangeInclusive::new(1, 0), |(min, max)| min.x..=max.x)
+    }
+
+    fn y_range(&self) -> RangeInclusive<isize> {
+        self.bounds.map_or(RangeInclusive::new(1, 0), |(min, max)| min.y..=max.y)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest and largest corners of the box holding every set cell,
+    /// inclusive. `None` while the grid is empty.
+    pub fn bounds(&self) -> Option<(Point2, Point2)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds.map_or(0, |(min, max)| max.x.abs_diff(min.x) + 1)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.map_or(0, |(min, max)| max.y.abs_diff(min.y) + 1)
+    }
+
+    /// The set cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> + '_ {
+        self.cells.iter().map(|(point, value)| (*point, value))
+    }
+
+    /// Every cell in row `y` across the bounding box, left to right.
+    pub fn row(&self, y: isize) -> impl Iterator<Item = &T> + '_ {
+        self.x_range().map(move |x| self.get(Point2::new(x, y)))
+    }
+
+    /// Every cell in column `x` across the bounding box, top to bottom.
+    pub fn column(&self, x: isize) -> impl Iterator<Item = &T> + '_ {
+        self.y_range().map(move |y| self.get(Point2::new(x, y)))
+    }
+
+    /// The rows of the bounding box, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        self.y_range().map(move |y| self.row(y))
+    }
+
+    /// The columns of the bounding box, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        self.x_range().map(move |x| self.column(x))
+    }
+}
+
+/// Draws only the bounding box, so an empty grid prints nothing.
+impl<T: ToGridChar> Debug for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                f.write_char(cell.to_grid_char())?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// The in-bounds neighbours of `point` in the given directions, for a grid of
+/// `(rows, cols)`.
+fn neighbors_within(
+    point: Point2,
+    dimensions: (usize, usize),
+    directions: &'static [Direction],
+) -> impl Iterator<Item = Point2> {
+    directions
+        .iter()
+        .filter_map(move |direction| point.checked_step(*direction))
+        .filter(move |neighbor| neighbor.within(dimensions).is_some())
+}
+
+/// Walks a straight line of `(row, col)` positions across a grid, starting at one
+/// edge. `dimensions` is the grid's `(rows, cols)`.
+pub struct LineIterator {
+    position: Point2,
+    direction: Direction,
+    dimensions: (usize, usize),
+}
+
+impl LineIterator {
+    fn new(direction: Direction, dimensions: (usize, usize), row: usize, col: usize) -> LineIterator {
         LineIterator {
-            orientation: LINE_ORIENTATION_RIGHT_TO_LEFT,
-            col: isize::try_from(S).expect("Grid size is too large") - 1,
-            row: isize::try_from(offset).expect("Offset is too large"),
+            position: Point2::from_row_col(row, col).expect("Offset is too large"),
+            direction,
+            dimensions,
         }
     }
+
+    pub fn get_line_from_top(dimensions: (usize, usize), offset: usize) -> LineIterator {
+        Self::new(Direction::Down, dimensions, 0, offset)
+    }
+
+    pub fn get_line_from_left(dimensions: (usize, usize), offset: usize) -> LineIterator {
+        Self::new(Direction::Right, dimensions, offset, 0)
+    }
+
+    pub fn get_line_from_bottom(dimensions: (usize, usize), offset: usize) -> LineIterator {
+        Self::new(Direction::Up, dimensions, dimensions.0.saturating_sub(1), offset)
+    }
+
+    pub fn get_line_from_right(dimensions: (usize, usize), offset: usize) -> LineIterator {
+        Self::new(Direction::Left, dimensions, offset, dimensions.1.saturating_sub(1))
+    }
 }
 
-impl<'a, const S: usize> Iterator for LineIterator<S> {
+impl Iterator for LineIterator {
     type Item = (usize, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let max_size = isize::try_from(S).expect("Grid size is too large");
-        if !(0..max_size).contains(&self.col) || !(0..max_size).contains(&self.row) {
-            return None;
-        }
-        let ret = (
-            usize::try_from(self.row).expect("Row size is too large"),
-            usize::try_from(self.col).expect("Column size is too large"),
-        );
-        self.col = self.col + self.orientation.col_step;
-        self.row = self.row + self.orientation.row_step;
+        let ret = self.position.within(self.dimensions)?;
+        self.position = self.position.step(self.direction);
         Some(ret)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_conversions() {
+        let square = SquareGrid::from_array([[1u8, 2], [3, 4]]);
+        let grid = Grid::from(square);
+        assert_eq!(grid[1][0], 3);
+        assert_eq!(format!("{:?}", grid.clone() * grid.clone()), "14\n9#\n");
+
+        let square: SquareGrid<u8, 2> = grid.try_into().unwrap();
+        assert_eq!(square[0], [1, 2]);
+        let wide = Grid::new_with(3, 2, 0u8);
+        assert!(SquareGrid::<u8, 2>::try_from(wide.clone()).is_err());
+        assert_eq!(Grid::from_rows(wide.into_rows()).unwrap().dimensions(), (2, 3));
+    }
+
+    #[test]
+    fn test_points_and_neighbors() {
+        let grid = Grid::from_rows(vec![vec![1u8, 2, 3], vec![4, 5, 6]]).unwrap();
+        let corner = Point2::new(2, 0);
+        assert_eq!(grid.get(corner), Some(&3));
+        assert_eq!(grid.get(corner.step(Direction::Right)), None);
+        assert_eq!(Point2::ORIGIN.checked_step(Direction::Up), Some(Point2::new(0, -1)));
+        assert_eq!(Point2::new(isize::MIN, 0).checked_step(Direction::Left), None);
+
+        let neighbors: Vec<_> = grid.neighbors4(corner).collect();
+        assert_eq!(neighbors, [Point2::new(2, 1), Point2::new(1, 0)]);
+        let neighbors: Vec<u8> = grid
+            .neighbors8(Point2::new(1, 1))
+            .map(|point| *grid.get(point).unwrap())
+            .collect();
+        assert_eq!(neighbors, [2, 3, 6, 4, 1]);
+    }
+
+    #[test]
+    fn test_direction_rotation() {
+        assert_eq!(Direction::Up.rotate_right(), Direction::Right);
+        assert_eq!(Direction::Up.rotate_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.rotate_right(), Direction::UpRight);
+        assert_eq!(Direction::DownRight.opposite(), Direction::UpLeft);
+        for direction in Direction::ALL {
+            assert_eq!(direction.offset() + direction.opposite().offset(), Point2::ORIGIN);
+            assert_eq!(direction.rotate_left().rotate_right(), direction);
+        }
+    }
+
+    #[test]
+    fn test_sparse_grid_bounds() {
+        let mut grid = SparseGrid::new(false);
+        assert_eq!(format!("{:?}", grid), "");
+        grid.insert(Point2::new(-2, -1), true);
+        grid.insert(Point2::new(1, 1), true);
+        grid.insert(Point2::new(0, 0), true);
+        assert_eq!(grid.bounds(), Some((Point2::new(-2, -1), Point2::new(1, 1))));
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(format!("{:?}", grid), "#...\n..#.\n...#\n");
+        assert!(*grid.get(Point2::new(1, 1)));
+        assert!(!*grid.get(Point2::new(100, -100)));
+
+        let column: Vec<bool> = grid.column(0).copied().collect();
+        assert_eq!(column, [false, true, false]);
+        assert_eq!(grid.columns().count(), 4);
+
+        grid.remove(Point2::new(-2, -1));
+        assert_eq!(grid.bounds(), Some((Point2::new(0, 0), Point2::new(1, 1))));
+        assert_eq!(format!("{:?}", grid), "#.\n.#\n");
+    }
+
+    #[test]
+    fn test_lines_on_rectangular_grid() {
+        let dimensions = (2, 3);
+        let from_right: Vec<_> = LineIterator::get_line_from_right(dimensions, 1).collect();
+        assert_eq!(from_right, [(1, 2), (1, 1), (1, 0)]);
+        let from_bottom: Vec<_> = LineIterator::get_line_from_bottom(dimensions, 2).collect();
+        assert_eq!(from_bottom, [(1, 2), (0, 2)]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,329 @@
+use std::fmt::{self, Debug, Write};
+
+use nom::{branch::alt, bytes::complete::tag, sequence::separated_pair, Parser};
+use num::PrimInt;
+
+use super::parsing::{integer, AocFormattable, AocParsable, ParsingResult, TextInteger};
+
+/// A range of integers where both `start` and `end` are included, matching how
+/// puzzles write ranges like `2-4` or `x=495..501`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    /// Creates an interval from two bounds given in either order. Reversed
+    /// bounds are swapped rather than rejected, so `new(5, 2)` is the same
+    /// interval as `new(2, 5)`; build the struct directly to keep them as given.
+    pub fn new(a: T, b: T) -> Self {
+        Interval {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    pub fn single(value: T) -> Self {
+        Interval {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub fn len(&self) -> T {
+        self.end - self.start + T::one()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// True if the intervals overlap or sit right next to each other, so that
+    /// their union is a single interval.
+    pub fn touches(&self, other: &Interval<T>) -> bool {
+        self.overlaps(other)
+            || self.end.checked_add(&T::one()) == Some(other.start)
+            || other.end.checked_add(&T::one()) == Some(self.start)
+    }
+
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.overlaps(other) {
+            Some(Interval {
+                start: self.start.max(other.start),
+                end: self.end.min(other.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Merges two touching intervals into one, or returns `None` if there is a gap.
+    pub fn merge(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.touches(other) {
+            Some(Interval {
+                start: self.start.min(other.start),
+                end: self.end.max(other.end),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Parses `a-b`, `a..b` or `a..=b`. All forms are treated as inclusive, and
+/// reversed bounds are swapped like `Interval::new` does, so `8-2` is written
+/// back as `2-8`.
+impl<T: TextInteger + PrimInt + Debug> AocParsable for Interval<T> {
+    fn parse_from_string(input: &[u8]) -> ParsingResult<'_, Interval<T>> {
+        separated_pair(
+            integer::<T>(),
+            alt((tag("..="), tag(".."), tag("-"))),
+            integer::<T>(),
+        )
+        .map(|(a, b)| Interval::new(a, b))
+        .parse(input)
+    }
+}
+
+impl<T: AocFormattable> AocFormattable for Interval<T> {
+    fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+        self.start.write_to(out)?;
+        out.write_char('-')?;
+        self.end.write_to(out)
+    }
+}
+
+/// A set of integers stored as sorted, disjoint and non-touching intervals.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().collect();
+        intervals.sort_by_key(|interval| interval.start);
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut().and_then(|last| last.merge(&interval)) {
+                Some(combined) => *merged.last_mut().unwrap() = combined,
+                None => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> + '_ {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers covered by the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |total, interval| total + interval.len())
+    }
+
+    /// Adds an interval, merging it with any intervals it touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let first = self.intervals.partition_point(|existing| {
+            existing.end < interval.start && !existing.touches(&interval)
+        });
+        let mut combined = interval;
+        let mut last = first;
+        while last < self.intervals.len() {
+            match self.intervals[last].merge(&combined) {
+                Some(merged) => combined = merged,
+                None => break,
+            }
+            last += 1;
+        }
+        self.intervals.splice(first..last, [combined]);
+    }
+
+    /// Removes every integer in the interval from the set, splitting intervals as needed.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        let mut remaining = Vec::with_capacity(self.intervals.len() + 1);
+        for existing in self.intervals.drain(..) {
+            if !existing.overlaps(&interval) {
+                remaining.push(existing);
+                continue;
+            }
+            if existing.start < interval.start {
+                remaining.push(Interval::new(existing.start, interval.start - T::one()));
+            }
+            if interval.end < existing.end {
+                remaining.push(Interval::new(interval.end + T::one(), existing.end));
+            }
+        }
+        self.intervals = remaining;
+    }
+
+    fn position_of(&self, value: T) -> Option<&Interval<T>> {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end < value);
+        self.intervals
+            .get(index)
+            .filter(|interval| interval.contains(value))
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.position_of(value).is_some()
+    }
+
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        self.position_of(interval.start)
+            .is_some_and(|found| found.contains_interval(interval))
+    }
+
+    pub fn overlaps(&self, interval: &Interval<T>) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|existing| existing.end < interval.start);
+        self.intervals
+            .get(index)
+            .is_some_and(|existing| existing.overlaps(interval))
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intersection = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (&self.intervals[i], &other.intervals[j]);
+            if let Some(overlap) = a.intersection(b) {
+                intersection.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet {
+            intervals: intersection,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::{parse_all, AocLineParsable};
+
+    #[derive(Debug, PartialEq, Eq, AocLineParsable)]
+    #[aoc(pattern = "{first},{second}")]
+    struct Pair {
+        first: Interval<u32>,
+        second: Interval<u32>,
+    }
+
+    #[test]
+    fn test_parse_interval_forms() {
+        let pairs: Vec<Pair> = parse_all(b"2-4,6..8\n5..=7,3-1\n").unwrap();
+        assert_eq!(pairs[0].first, Interval::new(2, 4));
+        assert_eq!(pairs[0].second, Interval::new(6, 8));
+        assert_eq!(pairs[1].first, Interval::new(5, 7));
+        assert_eq!(pairs[1].second, Interval::new(1, 3));
+
+        let (_, negative) = Interval::<i32>::parse_from_string(b"-5--2").unwrap();
+        assert_eq!(negative, Interval::new(-5, -2));
+        assert_eq!(negative.to_puzzle_string(), "-5--2");
+    }
+
+    #[test]
+    fn test_reversed_bounds_are_swapped() {
+        assert_eq!(Interval::new(5, 2), Interval { start: 2, end: 5 });
+        assert_eq!(Interval::new(-1, -1), Interval::single(-1));
+        let (_, reversed) = Interval::<u8>::parse_from_string(b"8-2").unwrap();
+        assert_eq!((reversed.start, reversed.end), (2, 8));
+        assert_eq!(reversed.to_puzzle_string(), "2-8");
+    }
+
+    #[test]
+    fn test_interval_relations() {
+        let a = Interval::new(2, 8);
+        let b = Interval::new(3, 7);
+        let c = Interval::new(9, 10);
+        assert!(a.contains_interval(&b) && !b.contains_interval(&a));
+        assert!(!a.overlaps(&c) && a.touches(&c));
+        assert_eq!(a.merge(&c), Some(Interval::new(2, 10)));
+        assert_eq!(b.merge(&c), None);
+        assert_eq!(a.intersection(&b), Some(b));
+        assert_eq!(a.len(), 7);
+    }
+
+    #[test]
+    fn test_interval_set_merges() {
+        let mut set = IntervalSet::new();
+        set.insert(Interval::new(10, 12));
+        set.insert(Interval::new(1, 3));
+        set.insert(Interval::new(5, 6));
+        set.insert(Interval::new(4, 4));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(1, 6), Interval::new(10, 12)]
+        );
+        assert_eq!(set.len(), 9);
+        assert!(set.contains(11) && !set.contains(8));
+        assert!(set.contains_interval(&Interval::new(2, 5)));
+        assert!(!set.contains_interval(&Interval::new(5, 10)));
+        assert!(set.overlaps(&Interval::new(7, 10)));
+        assert!(!set.overlaps(&Interval::new(7, 9)));
+
+        set.remove(Interval::new(3, 4));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![
+                Interval::new(1, 2),
+                Interval::new(5, 6),
+                Interval::new(10, 12)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interval_set_operations() {
+        let a: IntervalSet<i64> = [Interval::new(-10, 0), Interval::new(5, 20)]
+            .into_iter()
+            .collect();
+        let b: IntervalSet<i64> = [Interval::new(-2, 6), Interval::new(15, 30)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            a.union(&b).iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(-10, 30)]
+        );
+        assert_eq!(
+            a.intersection(&b).iter().copied().collect::<Vec<_>>(),
+            vec![
+                Interval::new(-2, 0),
+                Interval::new(5, 6),
+                Interval::new(15, 20)
+            ]
+        );
+    }
+}
@@ -1,4 +1,10 @@
+pub mod blueprints;
+pub mod expr;
 pub mod grids;
+pub mod intervals;
 pub mod iter;
+pub mod nested_list;
 pub mod parsing;
-pub mod shuffle_tree;
\ No newline at end of file
+pub mod shuffle_tree;
+pub mod symbols;
+pub mod vm;
\ No newline at end of file
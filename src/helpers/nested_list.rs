@@ -0,0 +1,203 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Write},
+};
+
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending},
+    multi::separated_list0,
+    sequence::{delimited, separated_pair},
+    Parser,
+};
+
+use super::parsing::{text_u32, AocBlockParsable, AocFormattable, AocParsable, ParsingResult};
+
+/// An integer or a bracketed list of further nested lists, like `[1,[2,[3]],4]`.
+///
+/// Equality is structural; use [`NestedList::packet_cmp`] or [`Packet`] for the packet ordering.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NestedList {
+    Integer(u32),
+    List(Vec<NestedList>),
+}
+
+impl AocParsable for NestedList {
+    fn parse_from_string(input: &[u8]) -> ParsingResult<'_, NestedList> {
+        alt((
+            text_u32().map(NestedList::Integer),
+            delimited(
+                char('['),
+                separated_list0(char(','), NestedList::parse_from_string),
+                char(']'),
+            )
+            .map(NestedList::List),
+        ))(input)
+    }
+}
+
+impl Display for NestedList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NestedList::Integer(value) => write!(f, "{}", value),
+            NestedList::List(children) => {
+                f.write_char('[')?;
+                for (i, child) in children.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", child)?;
+                }
+                f.write_char(']')
+            }
+        }
+    }
+}
+
+impl AocFormattable for NestedList {
+    fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl NestedList {
+    /// Compares two packets by the distress signal rules: integers compare by
+    /// value, lists compare element by element with the shorter list first on a
+    /// tie, and an integer compared with a list is treated as a list holding just
+    /// that integer. Unlike `==`, this treats `2` and `[2]` as equal.
+    pub fn packet_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (NestedList::Integer(a), NestedList::Integer(b)) => a.cmp(b),
+            (NestedList::List(a), NestedList::List(b)) => compare_lists(a, b),
+            (NestedList::Integer(_), NestedList::List(b)) => {
+                compare_lists(std::slice::from_ref(self), b)
+            }
+            (NestedList::List(a), NestedList::Integer(_)) => {
+                compare_lists(a, std::slice::from_ref(other))
+            }
+        }
+    }
+}
+
+fn compare_lists(left: &[NestedList], right: &[NestedList]) -> Ordering {
+    left.iter()
+        .zip(right)
+        .map(|(a, b)| a.packet_cmp(b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| left.len().cmp(&right.len()))
+}
+
+/// A nested list that compares by [`NestedList::packet_cmp`], so packets can be
+/// sorted, binary searched or kept in ordered collections directly.
+#[derive(Clone, Debug)]
+pub struct Packet(pub NestedList);
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.packet_cmp(&other.0)
+    }
+}
+
+impl AocParsable for Packet {
+    fn parse_from_string(input: &[u8]) -> ParsingResult<'_, Packet> {
+        NestedList::parse_from_string.map(Packet).parse(input)
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// Two nested lists on consecutive lines, with pairs separated by blank lines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NestedListPair(pub NestedList, pub NestedList);
+
+impl NestedListPair {
+    pub fn is_ordered(&self) -> bool {
+        self.0.packet_cmp(&self.1).is_le()
+    }
+}
+
+impl AocBlockParsable for NestedListPair {
+    fn parse_from_block(input: &[u8]) -> ParsingResult<'_, NestedListPair> {
+        separated_pair(
+            NestedList::parse_from_string,
+            line_ending,
+            NestedList::parse_from_string,
+        )
+        .map(|(left, right)| NestedListPair(left, right))
+        .parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::{parse_all_blocks, parse_complete};
+
+    const PACKETS: &str = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n\
+        [9]\n[[8,7,6]]\n\n[[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n\
+        []\n[3]\n\n[[[]]]\n[[]]\n\n[1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]\n";
+
+    fn parse(text: &str) -> NestedList {
+        parse_complete(text.as_bytes(), NestedList::parse_from_string).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let text = "[1,[2,[3,[4]]],5,[]]";
+        assert_eq!(parse(text).to_string(), text);
+        let pairs: Vec<NestedListPair> = parse_all_blocks(PACKETS.as_bytes()).unwrap();
+        let displayed: Vec<String> = pairs
+            .iter()
+            .flat_map(|pair| [pair.0.to_string(), pair.1.to_string()])
+            .collect();
+        let expected: Vec<&str> = PACKETS.lines().filter(|line| !line.is_empty()).collect();
+        assert_eq!(displayed, expected);
+    }
+
+    #[test]
+    fn test_packet_ordering() {
+        let pairs: Vec<NestedListPair> = parse_all_blocks(PACKETS.as_bytes()).unwrap();
+        let ordered: usize = pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, pair)| pair.is_ordered())
+            .map(|(i, _)| i + 1)
+            .sum();
+        assert_eq!(ordered, 13);
+
+        let dividers = [Packet(parse("[[2]]")), Packet(parse("[[6]]"))];
+        let mut packets: Vec<Packet> = pairs
+            .into_iter()
+            .flat_map(|pair| [Packet(pair.0), Packet(pair.1)])
+            .chain(dividers.iter().cloned())
+            .collect();
+        packets.sort();
+        let decoder_key: usize = dividers
+            .iter()
+            .map(|divider| packets.binary_search(divider).unwrap() + 1)
+            .product();
+        assert_eq!(decoder_key, 140);
+        assert_eq!(parse("2").packet_cmp(&parse("[[2]]")), Ordering::Equal);
+        assert_ne!(parse("2"), parse("[[2]]"));
+        assert_eq!(Packet(parse("2")), Packet(parse("[[2]]")));
+        assert!(Packet(parse("[]")) < Packet(parse("[[]]")));
+    }
+}
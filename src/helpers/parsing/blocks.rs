@@ -0,0 +1,151 @@
+use nom::{
+    character::complete::multispace0,
+    combinator::all_consuming,
+    error::{VerboseError, VerboseErrorKind},
+    multi::many0,
+    sequence::terminated,
+};
+
+use super::{parse_complete, AocParseError, ParsingError, ParsingResult, RecordIterator};
+
+/// Records that span several lines and are separated from each other by blank lines.
+pub trait AocBlockParsable: Sized + std::fmt::Debug {
+    /// Parses a single block. The input holds exactly one block without its final
+    /// line ending, and must be consumed completely.
+    fn parse_from_block(block: &[u8]) -> Result<(&[u8], Self), ParsingError<'_>>;
+}
+
+/// Lines with nothing but whitespace separate blocks, so stray `\r`s or
+/// trailing spaces don't matter.
+pub(super) fn is_blank(line: &[u8]) -> bool {
+    line.iter().all(u8::is_ascii_whitespace)
+}
+
+/// Splits a line off the input, returning it without its line ending
+/// along with the rest of the input after the line ending.
+pub(super) fn split_line(input: &[u8]) -> (&[u8], &[u8]) {
+    match input.iter().position(|c| *c == b'\n') {
+        Some(newline) => {
+            let line = &input[..newline];
+            (
+                line.strip_suffix(b"\r").unwrap_or(line),
+                &input[newline + 1..],
+            )
+        }
+        None => (input, &input[input.len()..]),
+    }
+}
+
+fn skip_blank_lines(mut input: &[u8]) -> &[u8] {
+    while !input.is_empty() {
+        let (line, rest) = split_line(input);
+        if !is_blank(line) {
+            break;
+        }
+        input = rest;
+    }
+    input
+}
+
+/// Takes the next block off the input, skipping any blank lines around it.
+fn take_block(input: &[u8]) -> ParsingResult<'_, &[u8]> {
+    let block_start = skip_blank_lines(input);
+    if block_start.is_empty() {
+        return Err(nom::Err::Error(VerboseError {
+            errors: vec![(block_start, VerboseErrorKind::Context("block"))],
+        }));
+    }
+
+    let mut block_len = 0;
+    let mut rest = block_start;
+    while !rest.is_empty() {
+        let (line, after_line) = split_line(rest);
+        if is_blank(line) {
+            break;
+        }
+        block_len = (line.as_ptr() as usize - block_start.as_ptr() as usize) + line.len();
+        rest = after_line;
+    }
+    Ok((skip_blank_lines(rest), &block_start[..block_len]))
+}
+
+/// Parses the next block of the input. Once a block has been split off, failing
+/// to parse it is reported as a `Failure` so that it isn't mistaken for the end of the list.
+pub fn parse_block<T: AocBlockParsable>(input: &[u8]) -> ParsingResult<'_, T> {
+    let (rest, block) = take_block(input)?;
+    match all_consuming(T::parse_from_block)(block) {
+        Ok((_, value)) => Ok((rest, value)),
+        Err(nom::Err::Error(err)) => Err(nom::Err::Failure(err)),
+        Err(err) => Err(err),
+    }
+}
+
+/// Blank lines before the first block are skipped up front, and `parse_block`
+/// skips those after each block, so blank-only input yields no records.
+pub fn iterate_blocks<T: AocBlockParsable>(input: &[u8]) -> RecordIterator<'_, T> {
+    RecordIterator::within(input, skip_blank_lines(input), parse_block::<T>)
+}
+
+pub fn parse_all_blocks<T: AocBlockParsable>(input: &[u8]) -> Result<Vec<T>, AocParseError> {
+    parse_complete(input, terminated(many0(parse_block::<T>), multispace0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::text_u32;
+    use nom::{character::complete::line_ending, multi::separated_list1, Parser};
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Numbers(Vec<u32>);
+
+    impl AocBlockParsable for Numbers {
+        fn parse_from_block(block: &[u8]) -> ParsingResult<'_, Numbers> {
+            separated_list1(line_ending, text_u32())
+                .map(Numbers)
+                .parse(block)
+        }
+    }
+
+    #[test]
+    fn test_separators_are_handled_the_same() {
+        let expected = vec![Numbers(vec![1, 2]), Numbers(vec![3])];
+        for input in [
+            "1\n2\n\n3",
+            "1\n2\n\n3\n",
+            "1\n2\n\n\n\n3\n\n",
+            "\n1\n2\n \n3",
+            "1\r\n2\r\n\r\n3\r\n",
+        ] {
+            assert_eq!(
+                parse_all_blocks::<Numbers>(input.as_bytes()).unwrap(),
+                expected
+            );
+        }
+        assert_eq!(parse_all_blocks::<Numbers>(b"\n\n").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_empty_input_has_no_blocks() {
+        for input in ["", "\n", "\n\n", " \r\n\t\n"] {
+            assert_eq!(
+                parse_all_blocks::<Numbers>(input.as_bytes()).unwrap(),
+                vec![]
+            );
+            assert_eq!(iterate_blocks::<Numbers>(input.as_bytes()).count(), 0);
+        }
+        let blocks: Result<Vec<Numbers>, _> = iterate_blocks(b"\n\n1\n2\n\n3\n\n\n").collect();
+        assert_eq!(blocks.unwrap(), vec![Numbers(vec![1, 2]), Numbers(vec![3])]);
+    }
+
+    #[test]
+    fn test_block_errors_are_positioned() {
+        let err = parse_all_blocks::<Numbers>(b"1\n2\n\n3\n4x\n\n5").unwrap_err();
+        assert_eq!((err.line(), err.column()), (5, 2));
+        let err = iterate_blocks::<Numbers>(b"\n\n4x\n")
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!((err.line(), err.column()), (3, 2));
+    }
+}
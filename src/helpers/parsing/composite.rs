@@ -0,0 +1,138 @@
+use nom::{
+    bytes::complete::tag,
+    combinator::opt,
+    multi::{many0, separated_list0, separated_list1},
+    Parser,
+};
+
+use super::{integer, AocParsable, ParsingResult, VerboseParsingError};
+
+macro_rules! impl_parsable_for_int {
+    ($($itype:ty),*) => {
+        $(
+            impl AocParsable for $itype {
+                const LIST_SEPARATOR: &'static str = ",";
+
+                fn parse_from_string(input: &[u8]) -> ParsingResult<'_, $itype> {
+                    integer::<$itype>().parse(input)
+                }
+            }
+        )*
+    };
+}
+
+impl_parsable_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_parsable_for_tuple {
+    ($($name:ident),*) => {
+        /// Parses each element in turn, with nothing in between.
+        impl<$($name: AocParsable),*> AocParsable for ($($name,)*) {
+            fn parse_from_string(input: &[u8]) -> ParsingResult<'_, ($($name,)*)> {
+                $(
+                    #[allow(non_snake_case)]
+                    let (input, $name) = $name::parse_from_string(input)?;
+                )*
+                Ok((input, ($($name,)*)))
+            }
+        }
+    };
+}
+
+impl_parsable_for_tuple!(A, B);
+impl_parsable_for_tuple!(A, B, C);
+impl_parsable_for_tuple!(A, B, C, D);
+
+/// Parses exactly `N` items, separated by `T::LIST_SEPARATOR`.
+impl<T: AocParsable, const N: usize> AocParsable for [T; N] {
+    fn parse_from_string(mut input: &[u8]) -> ParsingResult<'_, [T; N]> {
+        let mut items = Vec::with_capacity(N);
+        for i in 0..N {
+            if i > 0 {
+                (input, _) = tag(T::LIST_SEPARATOR)(input)?;
+            }
+            let (rest, item) = T::parse_from_string(input)?;
+            items.push(item);
+            input = rest;
+        }
+        Ok((input, items.try_into().unwrap()))
+    }
+}
+
+/// Parses items separated by `T::LIST_SEPARATOR` until one fails to parse, which
+/// may be none at all.
+impl<T: AocParsable> AocParsable for Vec<T> {
+    fn parse_from_string(input: &[u8]) -> ParsingResult<'_, Vec<T>> {
+        if T::LIST_SEPARATOR.is_empty() {
+            many0(T::parse_from_string)(input)
+        } else {
+            separated_list0(tag(T::LIST_SEPARATOR), T::parse_from_string)(input)
+        }
+    }
+}
+
+impl<T: AocParsable> AocParsable for Option<T> {
+    fn parse_from_string(input: &[u8]) -> ParsingResult<'_, Option<T>> {
+        opt(T::parse_from_string)(input)
+    }
+}
+
+/// Parses one or more `T`s with `separator` between them, such as `separated_by::<u32, _>(tag(", "))`.
+pub fn separated_by<'a, T: AocParsable, O>(
+    separator: impl Parser<&'a [u8], O, VerboseParsingError<'a>>,
+) -> impl Parser<&'a [u8], Vec<T>, VerboseParsingError<'a>> {
+    separated_list1(separator, T::parse_from_string)
+}
+
+/// Parses a `T` followed by `terminator`, such as a list item followed by a comma.
+pub fn terminated_by<'a, T: AocParsable, O>(
+    mut terminator: impl Parser<&'a [u8], O, VerboseParsingError<'a>>,
+) -> impl FnMut(&'a [u8]) -> ParsingResult<'a, T> {
+    move |input| {
+        let (input, item) = T::parse_from_string(input)?;
+        let (input, _) = terminator.parse(input)?;
+        Ok((input, item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::{parse_complete, AocLineParsable};
+
+    #[derive(Debug, PartialEq, Eq, AocLineParsable)]
+    #[aoc(pattern = "{name}={value}")]
+    struct Setting {
+        name: u8,
+        value: Option<i32>,
+    }
+
+    #[test]
+    fn test_composite_types() {
+        let input = b"1=5\n2=\n3=-7\n";
+        let settings: [Setting; 3] =
+            parse_complete(input, <[Setting; 3]>::parse_from_string).unwrap();
+        assert_eq!(
+            settings[1],
+            Setting {
+                name: 2,
+                value: None
+            }
+        );
+
+        let (settings, rest): (Vec<Setting>, Option<u32>) = parse_complete(
+            b"1=5\n2=6\n99",
+            <(Vec<Setting>, Option<u32>)>::parse_from_string,
+        )
+        .unwrap();
+        assert_eq!(settings.len(), 2);
+        assert_eq!(rest, Some(99));
+    }
+
+    #[test]
+    fn test_separated_by() {
+        let numbers: Vec<u32> = parse_complete(b"1, 2, 3", separated_by(tag(", "))).unwrap();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        let numbers: Vec<i8> = parse_complete(b"1;-2;", many0(terminated_by(tag(";")))).unwrap();
+        assert_eq!(numbers, vec![1, -2]);
+    }
+}
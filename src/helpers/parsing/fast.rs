@@ -0,0 +1,184 @@
+//! Fast paths for large inputs. These skip nom and UTF-8 validation entirely, and
+//! read digits eight at a time packed into a `u64`.
+use memchr::memchr_iter;
+use nom::{
+    error::{VerboseError, VerboseErrorKind},
+    Parser,
+};
+
+use super::{ParsingResult, VerboseParsingError};
+
+const ONES: u64 = 0x0101_0101_0101_0101;
+
+/// Byte offsets of every `\n` in the input.
+pub fn newline_positions(input: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    memchr_iter(b'\n', input)
+}
+
+/// Splits the input into lines without their `\n` or `\r\n` endings. A trailing
+/// newline doesn't produce an empty last line.
+pub fn split_lines(input: &[u8]) -> impl Iterator<Item = &[u8]> + '_ {
+    let mut start = 0;
+    newline_positions(input)
+        .chain((!input.is_empty() && input.last() != Some(&b'\n')).then_some(input.len()))
+        .map(move |end| {
+            let line = &input[start..end];
+            start = end + 1;
+            line.strip_suffix(b"\r").unwrap_or(line)
+        })
+}
+
+/// Loads up to eight bytes little-endian, so the first byte ends up in the lowest bits.
+fn load_chunk(bytes: &[u8]) -> u64 {
+    let mut chunk = [0u8; 8];
+    let len = bytes.len().min(8);
+    chunk[..len].copy_from_slice(&bytes[..len]);
+    u64::from_le_bytes(chunk)
+}
+
+/// Counts the ASCII digits at the start of a chunk of `valid` bytes.
+fn leading_digits(chunk: u64, valid: usize) -> usize {
+    // Digits become 0..=9 and everything else ends up above 9, which sets the top
+    // bit of the byte once 0x76 is added. Masking first keeps carries inside each byte.
+    let offset = chunk ^ (ONES * b'0' as u64);
+    let non_digits = (((offset & (ONES * 0x7f)) + ONES * 0x76) | offset) & (ONES * 0x80);
+    ((non_digits.trailing_zeros() / 8) as usize).min(valid)
+}
+
+/// Combines `len` leading digits of a chunk into their value, by summing neighbouring
+/// pairs of digits, then pairs of those, then pairs again.
+fn chunk_value(chunk: u64, len: usize) -> u64 {
+    if len == 0 {
+        return 0;
+    }
+    // Shift the digits to the top so missing leading digits read as zeros.
+    let chunk = (chunk.wrapping_sub(ONES * b'0' as u64)) << (8 * (8 - len));
+    let chunk = ((chunk & 0x0f00_0f00_0f00_0f00) >> 8) + (chunk & 0x000f_000f_000f_000f) * 10;
+    let chunk = ((chunk & 0x00ff_0000_00ff_0000) >> 16) + (chunk & 0x0000_00ff_0000_00ff) * 100;
+    ((chunk & 0x0000_ffff_0000_0000) >> 32) + (chunk & 0x0000_0000_0000_ffff) * 10000
+}
+
+/// Reads the unsigned number at the start of the input, returning it with the
+/// number of digits used, or `None` if there are no digits or it overflows a `u64`.
+pub fn parse_u64_swar(input: &[u8]) -> Option<(u64, usize)> {
+    let mut value: u64 = 0;
+    let mut len = 0;
+    loop {
+        let rest = &input[len..];
+        let chunk = load_chunk(rest);
+        let digits = leading_digits(chunk, rest.len());
+        if digits > 0 {
+            value = value
+                .checked_mul(10u64.pow(digits as u32))?
+                .checked_add(chunk_value(chunk, digits))?;
+            len += digits;
+        }
+        if digits < 8 {
+            break;
+        }
+    }
+    (len > 0).then_some((value, len))
+}
+
+/// Unsigned types with a fast SWAR parser.
+pub trait FastUnsigned: Sized {
+    const RANGE_CONTEXT: &'static str;
+
+    fn from_u64(value: u64) -> Option<Self>;
+}
+
+macro_rules! impl_fast_unsigned {
+    ($($utype:ty),*) => {
+        $(
+            impl FastUnsigned for $utype {
+                const RANGE_CONTEXT: &'static str = concat!(stringify!($utype), " in range");
+
+                fn from_u64(value: u64) -> Option<Self> {
+                    <$utype>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_fast_unsigned!(u8, u16, u32, u64);
+
+/// A drop-in replacement for `text_u8`..`text_u64` on plain unsigned numbers, without
+/// signs, prefixes or separators.
+pub fn fast_unsigned<'a, T: FastUnsigned>() -> impl Parser<&'a [u8], T, VerboseParsingError<'a>> {
+    |input: &'a [u8]| -> ParsingResult<'a, T> {
+        let error = |context| {
+            Err(nom::Err::Error(VerboseError {
+                errors: vec![(input, VerboseErrorKind::Context(context))],
+            }))
+        };
+        match parse_u64_swar(input) {
+            None if input.first().is_some_and(u8::is_ascii_digit) => error(T::RANGE_CONTEXT),
+            None => error("integer"),
+            Some((value, len)) => match T::from_u64(value) {
+                Some(value) => Ok((&input[len..], value)),
+                None => error(T::RANGE_CONTEXT),
+            },
+        }
+    }
+}
+
+/// Pulls every integer out of a line, ignoring whatever is around them. A `-` is a sign
+/// only when it doesn't follow a letter or digit, so `2-4` gives `2` and `4`.
+///
+/// Panics if a number doesn't fit in an `i64`.
+pub fn extract_all_ints(line: &[u8]) -> Vec<i64> {
+    let mut ints = vec![];
+    let mut i = 0;
+    while i < line.len() {
+        if !line[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let (value, len) = parse_u64_swar(&line[i..]).expect("integer out of range");
+        let negative =
+            i > 0 && line[i - 1] == b'-' && (i < 2 || !line[i - 2].is_ascii_alphanumeric());
+        let value = i64::try_from(value).expect("integer out of range");
+        ints.push(if negative { -value } else { value });
+        i += len;
+    }
+    ints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::parse_complete;
+
+    #[test]
+    fn test_parse_u64_swar() {
+        for value in [0, 7, 42, 12345678, 123456789, 98765432101234, u64::MAX] {
+            let text = format!("{}x", value);
+            assert_eq!(
+                parse_u64_swar(text.as_bytes()),
+                Some((value, text.len() - 1))
+            );
+        }
+        assert_eq!(parse_u64_swar(b"007"), Some((7, 3)));
+        assert_eq!(parse_u64_swar(b"x1"), None);
+        assert_eq!(parse_u64_swar(b"18446744073709551616"), None);
+    }
+
+    #[test]
+    fn test_fast_unsigned() {
+        assert_eq!(parse_complete(b"255", fast_unsigned::<u8>()).unwrap(), 255);
+        let err = parse_complete(b"256", fast_unsigned::<u8>()).unwrap_err();
+        assert!(err.to_string().starts_with("expected u8 in range"));
+    }
+
+    #[test]
+    fn test_lines_and_ints() {
+        let lines: Vec<&[u8]> = split_lines(b"a\r\nb\n\nc").collect();
+        assert_eq!(lines, vec![&b"a"[..], b"b", b"", b"c"]);
+        assert_eq!(split_lines(b"a\n").count(), 1);
+        assert_eq!(
+            extract_all_ints(b"Sensor at x=-2, y=15: 2-4,6-8 beacon-3"),
+            vec![-2, 15, 2, 4, 6, 8, 3]
+        );
+    }
+}
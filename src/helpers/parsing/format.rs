@@ -0,0 +1,246 @@
+use std::{
+    fmt::{self, Debug, Write},
+    io,
+};
+
+use super::{parse_complete, AocParsable};
+
+/// The inverse of `AocParsable`: writes a value back out in puzzle format.
+pub trait AocFormattable {
+    fn write_to(&self, out: &mut impl Write) -> fmt::Result;
+
+    fn to_puzzle_string(&self) -> String {
+        let mut text = String::new();
+        self.write_to(&mut text)
+            .expect("Writing to a String can't fail");
+        text
+    }
+
+    /// Writes straight to a file or other byte sink, such as when generating inputs.
+    fn write_io(&self, out: &mut impl io::Write) -> io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: out,
+            error: None,
+        };
+        self.write_to(&mut adapter).map_err(|_| {
+            adapter
+                .error
+                .take()
+                .unwrap_or_else(|| io::Error::other("formatter error"))
+        })
+    }
+}
+
+/// The inverse of `AocLineParsable`. Line records are written with their line
+/// ending, so consecutive records parse back as separate lines.
+pub trait AocLineFormattable {
+    fn write_line(&self, out: &mut impl Write) -> fmt::Result;
+}
+
+impl<T: AocLineFormattable> AocFormattable for T {
+    fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+        self.write_line(out)?;
+        out.write_char('\n')
+    }
+}
+
+/// Passes text on to an `io::Write`, keeping the I/O error that `fmt::Error` can't carry.
+struct IoAdapter<'w, W: io::Write> {
+    inner: &'w mut W,
+    error: Option<io::Error>,
+}
+
+impl<'w, W: io::Write> Write for IoAdapter<'w, W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.inner.write_all(text.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+macro_rules! impl_formattable_for_int {
+    ($($itype:ty),*) => {
+        $(
+            impl AocFormattable for $itype {
+                fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+                    write!(out, "{}", self)
+                }
+            }
+        )*
+    };
+}
+
+impl_formattable_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_formattable_for_tuple {
+    ($($name:ident: $index:tt),*) => {
+        impl<$($name: AocFormattable),*> AocFormattable for ($($name,)*) {
+            fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+                $(self.$index.write_to(out)?;)*
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_formattable_for_tuple!(A: 0, B: 1);
+impl_formattable_for_tuple!(A: 0, B: 1, C: 2);
+impl_formattable_for_tuple!(A: 0, B: 1, C: 2, D: 3);
+
+/// Items are separated by `T::LIST_SEPARATOR`, matching how `Vec<T>` is parsed.
+impl<T: AocParsable + AocFormattable> AocFormattable for [T] {
+    fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                out.write_str(T::LIST_SEPARATOR)?;
+            }
+            item.write_to(out)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: AocParsable + AocFormattable, const N: usize> AocFormattable for [T; N] {
+    fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+        self.as_slice().write_to(out)
+    }
+}
+
+impl<T: AocParsable + AocFormattable> AocFormattable for Vec<T> {
+    fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+        self.as_slice().write_to(out)
+    }
+}
+
+impl<T: AocFormattable> AocFormattable for Option<T> {
+    fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+        match self {
+            Some(value) => value.write_to(out),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes one record per line, the inverse of `parse_all` for line records.
+pub fn format_lines<'a, T: AocLineFormattable + 'a>(
+    records: impl IntoIterator<Item = &'a T>,
+) -> String {
+    let mut text = String::new();
+    for record in records {
+        record
+            .write_to(&mut text)
+            .expect("Writing to a String can't fail");
+    }
+    text
+}
+
+/// Checks that every value survives being formatted and parsed again, for use in
+/// tests over a range of generated or example values.
+pub fn assert_round_trips<T>(values: impl IntoIterator<Item = T>)
+where
+    T: AocParsable + AocFormattable + PartialEq + Debug,
+{
+    for value in values {
+        let text = value.to_puzzle_string();
+        let parsed = parse_complete(text.as_bytes(), T::parse_from_string)
+            .unwrap_or_else(|err| panic!("{:?} was formatted as {:?}: {}", value, text, err));
+        assert_eq!(parsed, value, "round trip through {:?}", text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::{
+        AocFormattable, AocLineFormattable, AocLineParsable, AocParsable,
+    };
+
+    #[derive(Debug, PartialEq, Eq, AocLineParsable, AocLineFormattable)]
+    #[aoc(pattern = "{name}: {{{first},{second}}}")]
+    struct Braced {
+        name: u8,
+        first: i32,
+        second: Option<u16>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, AocParsable, AocFormattable)]
+    #[aoc(pattern = "{ore} ore and {clay} clay")]
+    struct Cost {
+        ore: u8,
+        clay: u8,
+    }
+
+    #[derive(Debug, PartialEq, Eq, AocLineParsable, AocLineFormattable)]
+    #[aoc(
+        pattern = "Blueprint {number}: Each robot costs {robot}. Each geode robot costs {geode}."
+    )]
+    struct Blueprint {
+        number: u8,
+        robot: Cost,
+        geode: Cost,
+    }
+
+    fn braced(i: i32) -> Braced {
+        Braced {
+            name: (i & 0xff) as u8,
+            first: i * 7,
+            second: (i > 0).then_some(i as u16),
+        }
+    }
+
+    #[test]
+    fn test_derived_format() {
+        assert_eq!(braced(-4).to_puzzle_string(), "252: {-28,}\n");
+        assert_eq!(
+            format_lines(&[braced(-4), braced(1)]),
+            "252: {-28,}\n1: {7,1}\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trips() {
+        assert_round_trips((-300..300).map(braced));
+    }
+
+    #[test]
+    fn test_lists_round_trip() {
+        assert_eq!(vec![1u32, 2u32].to_puzzle_string(), "1,2");
+        assert_round_trips([vec![1u32, 2u32], vec![], vec![7]]);
+        assert_round_trips([[-1i8, 0, 1]]);
+        assert_round_trips([(-3..3).map(braced).collect::<Vec<_>>()]);
+    }
+
+    #[test]
+    fn test_nested_records_round_trip() {
+        let blueprint = Blueprint {
+            number: 1,
+            robot: Cost { ore: 4, clay: 0 },
+            geode: Cost { ore: 2, clay: 7 },
+        };
+        assert_eq!(
+            blueprint.to_puzzle_string(),
+            "Blueprint 1: Each robot costs 4 ore and 0 clay. \
+            Each geode robot costs 2 ore and 7 clay.\n"
+        );
+        assert_round_trips((0..=255).map(|i| Blueprint {
+            number: i,
+            robot: Cost { ore: i, clay: !i },
+            geode: Cost {
+                ore: i / 3,
+                clay: i % 7,
+            },
+        }));
+    }
+
+    #[test]
+    fn test_write_io() {
+        let mut bytes: Vec<u8> = vec![];
+        vec![braced(2), braced(3)].write_io(&mut bytes).unwrap();
+        assert_eq!(bytes, b"2: {14,2}\n3: {21,3}\n");
+
+        let mut full = [0u8; 4];
+        let err = braced(2).write_io(&mut &mut full[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+}
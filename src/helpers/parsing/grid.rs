@@ -0,0 +1,136 @@
+use std::fmt::Display;
+
+use crate::helpers::grids::{Grid, SquareGrid};
+
+use super::AocParseError;
+
+/// A grid read from the input, with its size taken from the input itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsedGrid<T> {
+    pub rows: Vec<Vec<T>>,
+    pub width: usize,
+    pub height: usize,
+    markers: Vec<(u8, (usize, usize))>,
+}
+
+impl<T> ParsedGrid<T> {
+    /// The `(row, col)` of the first occurrence of a marker byte.
+    pub fn marker(&self, marker: u8) -> Option<(usize, usize)> {
+        self.markers(marker).next()
+    }
+
+    /// The `(row, col)` of every occurrence of a marker byte, in reading order.
+    pub fn markers(&self, marker: u8) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.markers
+            .iter()
+            .filter(move |(byte, _)| *byte == marker)
+            .map(|(_, position)| *position)
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        Grid::from_rows(self.rows).expect("Parsed grids are always rectangular")
+    }
+
+    /// Converts into a `SquareGrid`, or returns `None` if the grid isn't `S` by `S`.
+    pub fn into_square_grid<const S: usize>(self) -> Option<SquareGrid<T, S>> {
+        let rows: Vec<[T; S]> = self
+            .rows
+            .into_iter()
+            .map(|row| row.try_into().ok())
+            .collect::<Option<_>>()?;
+        Some(SquareGrid::from_array(rows.try_into().ok()?))
+    }
+}
+
+/// Parses a rectangular grid, converting each byte with `cell`. Rows may end in
+/// `\n` or `\r\n` and trailing blank lines are ignored, but every row must be
+/// as wide as the first.
+pub fn parse_grid<T, E: Display>(
+    input: &[u8],
+    cell: impl FnMut(u8) -> Result<T, E>,
+) -> Result<ParsedGrid<T>, AocParseError> {
+    parse_grid_with_markers(input, &[], cell)
+}
+
+/// Like `parse_grid`, but also records where each of the `markers` bytes appears.
+/// Markers are still passed to `cell`, so `S` can become an ordinary cell value.
+pub fn parse_grid_with_markers<T, E: Display>(
+    input: &[u8],
+    markers: &[u8],
+    mut cell: impl FnMut(u8) -> Result<T, E>,
+) -> Result<ParsedGrid<T>, AocParseError> {
+    let mut lines: Vec<&[u8]> = input
+        .split(|c| *c == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    let width = lines.first().map_or(0, |line| line.len());
+    let mut grid = ParsedGrid {
+        rows: Vec::with_capacity(lines.len()),
+        width,
+        height: lines.len(),
+        markers: vec![],
+    };
+    for (row, line) in lines.into_iter().enumerate() {
+        if line.len() != width {
+            return Err(AocParseError::at(
+                input,
+                &line[width.min(line.len())..],
+                format!("expected a row of width {}, found {}", width, line.len()),
+            ));
+        }
+        let mut cells = Vec::with_capacity(width);
+        for (col, byte) in line.iter().enumerate() {
+            if markers.contains(byte) {
+                grid.markers.push((*byte, (row, col)));
+            }
+            let value = cell(*byte)
+                .map_err(|err| AocParseError::at(input, &line[col..], err.to_string()))?;
+            cells.push(value);
+        }
+        grid.rows.push(cells);
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digit(c: u8) -> Result<u8, &'static str> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            _ => Err("expected a digit"),
+        }
+    }
+
+    #[test]
+    fn test_parse_grid_infers_size() {
+        let grid = parse_grid(b"123\r\n456\r\n\r\n", digit).unwrap();
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid.rows, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        let err = parse_grid(b"123\n45\n678\n", digit).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+        let err = parse_grid(b"123\n4x6\n", digit).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
+    }
+
+    #[test]
+    fn test_parse_grid_markers() {
+        let grid = parse_grid_with_markers(b"Sab\nbcE\n", b"SE", |c| {
+            Ok::<u8, &str>(match c {
+                b'S' => b'a',
+                b'E' => b'z',
+                c => c,
+            })
+        })
+        .unwrap();
+        assert_eq!(grid.marker(b'S'), Some((0, 0)));
+        assert_eq!(grid.marker(b'E'), Some((1, 2)));
+        assert_eq!(grid.rows[1], b"bcz".to_vec());
+    }
+}
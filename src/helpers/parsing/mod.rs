@@ -0,0 +1,456 @@
+use std::{
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+};
+
+use nom::sequence::terminated;
+use nom::{
+    branch::alt,
+    character::complete::line_ending,
+    combinator::{all_consuming, eof},
+    error::{ErrorKind, ParseError, VerboseError, VerboseErrorKind},
+    multi::many0,
+    Err, Parser,
+};
+
+mod blocks;
+mod composite;
+mod fast;
+mod format;
+mod grid;
+mod numbers;
+mod parallel;
+mod stream;
+mod table;
+
+pub use advent_of_code_derive::{
+    AocBlockParsable, AocFormattable, AocLineFormattable, AocLineParsable, AocParsable,
+};
+pub use blocks::{iterate_blocks, parse_all_blocks, parse_block, AocBlockParsable};
+pub use composite::{separated_by, terminated_by};
+pub use fast::{
+    extract_all_ints, fast_unsigned, newline_positions, parse_u64_swar, split_lines, FastUnsigned,
+};
+pub use format::{assert_round_trips, format_lines, AocFormattable, AocLineFormattable};
+pub use grid::{parse_grid, parse_grid_with_markers, ParsedGrid};
+pub use numbers::{
+    binary_integer, float, grouped_integer, hex_integer, integer, integer_radix, text_i16,
+    text_i32, text_i64, text_i8, text_isize, text_u16, text_u32, text_u64, text_u8, text_usize,
+    TextInteger,
+};
+pub use parallel::par_parse_all;
+pub use stream::{
+    find_window, stream_blocks, stream_records, BlockStream, RecordStream, StreamError,
+};
+pub use table::{parse_table, LabelLine, Table};
+
+pub type VerboseParsingError<'a> = VerboseError<&'a [u8]>;
+
+pub type ParsingError<'a> = nom::Err<VerboseParsingError<'a>>;
+
+pub type ParsingResult<'a, O> = Result<(&'a [u8], O), ParsingError<'a>>;
+
+pub trait AocParsable: Sized + Debug {
+    /// Expected between the items of a `Vec` or array of these, and written between
+    /// them when formatting. Records that end themselves, like lines, need none.
+    const LIST_SEPARATOR: &'static str = "";
+
+    fn parse_from_string(input: &[u8]) -> Result<(&[u8], Self), ParsingError>;
+}
+
+pub trait AocLineParsable: Sized + Debug {
+    fn parse_from_line(input: &[u8]) -> Result<(&[u8], Self), ParsingError>;
+}
+
+impl<T: AocLineParsable> AocParsable for T {
+    fn parse_from_string(input: &[u8]) -> Result<(&[u8], Self), ParsingError> {
+        terminated(Self::parse_from_line, line_ending_or_eof())(input)
+    }
+}
+
+pub struct AocParser<T: AocParsable> {
+    output: PhantomData<T>,
+}
+
+impl<'a, T: AocParsable> Parser<&'a [u8], T, VerboseParsingError<'a>> for AocParser<T> {
+    fn parse(&mut self, input: &'a [u8]) -> nom::IResult<&'a [u8], T, VerboseParsingError<'a>> {
+        T::parse_from_string(input)
+    }
+}
+
+pub fn parse_all<T: AocParsable>(input: &[u8]) -> Result<Vec<T>, AocParseError> {
+    let parser = AocParser {
+        output: PhantomData,
+    };
+    parse_complete(input, many0(parser))
+}
+
+/// Runs a parser that must consume the whole input, turning failures into an
+/// `AocParseError` that points at the offending line.
+pub fn parse_complete<'a, O>(
+    input: &'a [u8],
+    parser: impl Parser<&'a [u8], O, VerboseParsingError<'a>>,
+) -> Result<O, AocParseError> {
+    all_consuming(parser)(input)
+        .map(|(_, result)| result)
+        .map_err(|err| AocParseError::new(input, err))
+}
+
+/// Iterates over the records in the input. Unlike a plain nom iterator, a record
+/// that fails to parse, or input left over after the last record, is yielded as an
+/// error instead of quietly ending the iteration.
+pub fn iterate_all<T: AocParsable>(input: &[u8]) -> RecordIterator<'_, T> {
+    RecordIterator::new(input, T::parse_from_string)
+}
+
+pub struct RecordIterator<'a, T> {
+    original: &'a [u8],
+    remaining: &'a [u8],
+    parser: fn(&'a [u8]) -> ParsingResult<'a, T>,
+    finished: bool,
+}
+
+impl<'a, T> RecordIterator<'a, T> {
+    pub fn new(input: &'a [u8], parser: fn(&'a [u8]) -> ParsingResult<'a, T>) -> Self {
+        RecordIterator {
+            original: input,
+            remaining: input,
+            parser,
+            finished: false,
+        }
+    }
+
+    /// Iterates over the records in `chunk`, a piece of `original`, reporting
+    /// error positions relative to all of `original`.
+    pub(crate) fn within(
+        original: &'a [u8],
+        chunk: &'a [u8],
+        parser: fn(&'a [u8]) -> ParsingResult<'a, T>,
+    ) -> Self {
+        RecordIterator {
+            original,
+            remaining: chunk,
+            parser,
+            finished: false,
+        }
+    }
+
+    /// Checks that every record parsed, without keeping them.
+    pub fn finish(self) -> Result<(), AocParseError> {
+        for record in self {
+            record?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> Iterator for RecordIterator<'a, T> {
+    type Item = Result<T, AocParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished || self.remaining.is_empty() {
+            self.finished = true;
+            return None;
+        }
+        match (self.parser)(self.remaining) {
+            Ok((rest, _)) if rest.len() == self.remaining.len() => {
+                self.finished = true;
+                Some(Err(AocParseError::at(
+                    self.original,
+                    self.remaining,
+                    "record parser consumed no input".to_string(),
+                )))
+            }
+            Ok((rest, record)) => {
+                self.remaining = rest;
+                Some(Ok(record))
+            }
+            Err(err) => {
+                self.finished = true;
+                Some(Err(AocParseError::new(self.original, err)))
+            }
+        }
+    }
+}
+
+impl<'a, T> std::iter::FusedIterator for RecordIterator<'a, T> {}
+
+pub fn line_ending_or_eof<'a, E: ParseError<&'a [u8]>>() -> impl Parser<&'a [u8], &'a [u8], E> {
+    alt((line_ending, eof))
+}
+
+pub fn generic_error_for_input<T>(input: &[u8]) -> Result<T, ParsingError> {
+    Err(nom::Err::Error(VerboseError::from_error_kind(
+        input,
+        ErrorKind::Fail,
+    )))
+}
+
+/// Like `generic_error_for_input`, but describes what was expected at this point.
+pub fn expected_error_for_input<'a, T>(
+    input: &'a [u8],
+    expected: &'static str,
+) -> Result<T, ParsingError<'a>> {
+    Err(nom::Err::Error(VerboseError {
+        errors: vec![(input, VerboseErrorKind::Context(expected))],
+    }))
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct ErrorLocation {
+    line: usize,
+    column: usize,
+    line_text: String,
+}
+
+impl ErrorLocation {
+    /// Errors point into the original input, either as a suffix or as a slice
+    /// of a block that was split off, so the offset is recovered from the pointers.
+    fn find(original: &[u8], remaining: &[u8]) -> ErrorLocation {
+        let start = original.as_ptr() as usize;
+        let offset = match (remaining.as_ptr() as usize).checked_sub(start) {
+            Some(offset) if offset <= original.len() => offset,
+            _ => original.len().saturating_sub(remaining.len()),
+        };
+        let before = &original[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|c| *c == b'\n')
+            .map_or(0, |newline| newline + 1);
+        let line_end = original[offset..]
+            .iter()
+            .position(|c| *c == b'\n')
+            .map_or(original.len(), |newline| offset + newline);
+        ErrorLocation {
+            line: before.iter().filter(|c| **c == b'\n').count() + 1,
+            column: String::from_utf8_lossy(&original[line_start..offset])
+                .chars()
+                .count()
+                + 1,
+            line_text: String::from_utf8_lossy(&original[line_start..line_end])
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+}
+
+/// A parsing failure that knows where in the original input it happened,
+/// printing the offending line with a caret and the stack of `context`s.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AocParseError {
+    location: ErrorLocation,
+    message: String,
+    contexts: Vec<(&'static str, ErrorLocation)>,
+}
+
+impl AocParseError {
+    pub fn new(original: &[u8], err: ParsingError) -> AocParseError {
+        let verbose = match err {
+            Err::Incomplete(_) => {
+                return AocParseError {
+                    location: ErrorLocation::find(original, &[]),
+                    message: "unexpected end of input".to_string(),
+                    contexts: vec![],
+                };
+            }
+            Err::Error(verbose) | Err::Failure(verbose) => verbose,
+        };
+
+        // The first entry is where parsing actually failed. Outer entries only add
+        // the kinds of the combinators it failed inside of, like `alt` or `tag`,
+        // which would hide the real cause, so only their `context` labels are kept.
+        let mut errors = verbose.errors.iter();
+        let (location, message) = match errors.next() {
+            Some((remaining, kind)) => (
+                ErrorLocation::find(original, remaining),
+                Self::describe(kind),
+            ),
+            None => (
+                ErrorLocation::find(original, original),
+                "parse error".to_string(),
+            ),
+        };
+        let contexts = errors
+            .filter_map(|(remaining, kind)| match kind {
+                VerboseErrorKind::Context(context) => {
+                    Some((*context, ErrorLocation::find(original, remaining)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        AocParseError {
+            location,
+            message,
+            contexts,
+        }
+    }
+
+    fn describe(kind: &VerboseErrorKind) -> String {
+        match kind {
+            VerboseErrorKind::Context(context) => format!("expected {}", context),
+            VerboseErrorKind::Char(c) => format!("expected '{}'", c),
+            VerboseErrorKind::Nom(ErrorKind::Eof) => "expected end of input".to_string(),
+            VerboseErrorKind::Nom(kind) => format!("failed at {}", kind.description()),
+        }
+    }
+
+    /// An error for checks done outside of nom, pointing at `remaining` in `original`.
+    pub(crate) fn at(original: &[u8], remaining: &[u8], message: String) -> AocParseError {
+        AocParseError {
+            location: ErrorLocation::find(original, remaining),
+            message,
+            contexts: vec![],
+        }
+    }
+
+    /// Moves the error down by `lines`, for input that was parsed one piece at a time.
+    pub(crate) fn with_line_offset(mut self, lines: usize) -> AocParseError {
+        self.location.line += lines;
+        for (_, location) in self.contexts.iter_mut() {
+            location.line += lines;
+        }
+        self
+    }
+
+    pub fn line(&self) -> usize {
+        self.location.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.location.column
+    }
+
+    pub fn contexts(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.contexts.iter().map(|(context, _)| *context)
+    }
+}
+
+impl Display for AocParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location = &self.location;
+        let gutter = " ".repeat(location.line.to_string().len());
+        writeln!(
+            f,
+            "{} at line {}, column {}",
+            self.message, location.line, location.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", location.line, location.line_text)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(location.column - 1))?;
+        for (context, context_location) in self.contexts.iter() {
+            write!(
+                f,
+                "\n{} = while parsing {} starting at line {}, column {}",
+                gutter, context, context_location.line, context_location.column
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Shares the `Display` output so that `.unwrap()` and `.expect()` print a readable report.
+impl Debug for AocParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\n{}", self)
+    }
+}
+
+impl std::error::Error for AocParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{
+        bytes::complete::tag,
+        character::complete::char,
+        error::context,
+        sequence::{preceded, tuple},
+    };
+
+    #[derive(Debug, PartialEq, Eq, AocLineParsable)]
+    #[aoc(pattern = "move {count} from {from} to {to}")]
+    struct Move {
+        count: u8,
+        from: usize,
+        to: i32,
+    }
+
+    #[test]
+    fn test_derive_from_pattern() {
+        let moves: Vec<Move> = parse_all(b"move 1 from 2 to -1\nmove 3 from 1 to 3").unwrap();
+        assert_eq!(
+            moves,
+            vec![
+                Move {
+                    count: 1,
+                    from: 2,
+                    to: -1
+                },
+                Move {
+                    count: 3,
+                    from: 1,
+                    to: 3
+                }
+            ]
+        );
+
+        let err = parse_complete(b"move 3 to 1", Move::parse_from_line).unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 7));
+        assert_eq!(err.contexts().collect::<Vec<_>>(), vec!["Move"]);
+    }
+
+    #[test]
+    fn test_error_reports_line_and_column() {
+        let input = b"move 1 from 2\nmove x from 3\n";
+        let line = |input| -> ParsingResult<(u8, u8)> {
+            context(
+                "move",
+                terminated(
+                    tuple((tag("move "), text_u8(), tag(" from "), text_u8())),
+                    line_ending,
+                ),
+            )(input)
+            .map(|(rest, (_, count, _, from))| (rest, (count, from)))
+        };
+        let err = parse_complete(input, many0(line)).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 1));
+
+        let err = parse_complete(input, tuple((line, line))).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 6));
+        assert_eq!(err.contexts().collect::<Vec<_>>(), vec!["move"]);
+        assert_eq!(
+            err.to_string(),
+            "expected integer at line 2, column 6\n  |\n2 | move x from 3\n  |      ^\n  = while parsing move starting at line 2, column 1"
+        );
+    }
+
+    #[test]
+    fn test_error_message_comes_from_innermost_failure() {
+        let input = b"x = c";
+        let value = context("value", alt((char('a'), char('b'))));
+        let err = parse_complete(input, preceded(tag("x = "), value)).unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 5));
+        assert_eq!(err.contexts().collect::<Vec<_>>(), vec!["value"]);
+        assert!(err
+            .to_string()
+            .starts_with("expected 'b' at line 1, column 5"));
+    }
+
+    #[test]
+    fn test_iterate_all_reports_bad_records() {
+        let input = b"move 1 from 2 to 3\nmove 2 from x to 1\nmove 3 from 1 to 2\n";
+        let mut records = iterate_all::<Move>(input);
+        assert!(records.next().unwrap().is_ok());
+        let err = records.next().unwrap().unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 13));
+        assert!(records.next().is_none());
+
+        assert!(iterate_all::<Move>(b"move 1 from 2 to 3\n")
+            .finish()
+            .is_ok());
+        assert!(iterate_all::<Move>(b"move 1 from 2 to 3\n?")
+            .finish()
+            .is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use std::str::{self, FromStr};
+
+use nom::{
+    error::{VerboseError, VerboseErrorKind},
+    Parser,
+};
+
+use super::{ParsingResult, VerboseParsingError};
+
+/// Integer types that can be read from puzzle text by `integer` and friends.
+pub trait TextInteger: FromStr + Sized {
+    const SIGNED: bool;
+    /// Reported when the digits are well formed but don't fit into the type.
+    const RANGE_CONTEXT: &'static str;
+    const ZERO: Self;
+
+    /// Appends a digit to the number read so far, returning `None` on overflow.
+    /// Negative numbers subtract their digits, so the type's minimum fits too.
+    fn push_digit(self, digit: u32, radix: u32, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_text_integer {
+    ($($itype:ty: $signed:expr),*) => {
+        $(
+            impl TextInteger for $itype {
+                const SIGNED: bool = $signed;
+                const RANGE_CONTEXT: &'static str = concat!(stringify!($itype), " in range");
+                const ZERO: Self = 0;
+
+                fn push_digit(self, digit: u32, radix: u32, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(radix as $itype)?;
+                    if negative {
+                        shifted.checked_sub(digit as $itype)
+                    } else {
+                        shifted.checked_add(digit as $itype)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_text_integer!(u8: false, u16: false, u32: false, u64: false, u128: false, usize: false);
+impl_text_integer!(i8: true, i16: true, i32: true, i64: true, i128: true, isize: true);
+
+fn error_at<'a, O>(input: &'a [u8], context: &'static str) -> ParsingResult<'a, O> {
+    Err(nom::Err::Error(VerboseError {
+        errors: vec![(input, VerboseErrorKind::Context(context))],
+    }))
+}
+
+fn scan_sign(input: &[u8]) -> usize {
+    match input.first() {
+        Some(b'-' | b'+') => 1,
+        _ => 0,
+    }
+}
+
+/// Counts the digits at the start of the input. A separator is only accepted
+/// between two digits, so `1,000` is one number but `1, 2` stops after the `1`.
+fn scan_digits(input: &[u8], radix: u32, separator: Option<u8>) -> usize {
+    let is_digit = |c: &u8| (*c as char).is_digit(radix);
+    let mut len = 0;
+    while len < input.len() {
+        let is_separator =
+            len > 0 && Some(input[len]) == separator && input.get(len + 1).is_some_and(is_digit);
+        if !is_digit(&input[len]) && !is_separator {
+            break;
+        }
+        len += 1;
+    }
+    len
+}
+
+fn scan_prefix(input: &[u8], prefixes: &[&str]) -> usize {
+    prefixes
+        .iter()
+        .find(|prefix| {
+            input.len() >= prefix.len()
+                && input[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+        })
+        .map_or(0, |prefix| prefix.len())
+}
+
+fn integer_with<'a, T: TextInteger>(
+    radix: u32,
+    prefixes: &'static [&'static str],
+    separator: Option<u8>,
+    expected: &'static str,
+) -> impl Parser<&'a [u8], T, VerboseParsingError<'a>> {
+    move |input: &'a [u8]| {
+        let sign_len = if T::SIGNED { scan_sign(input) } else { 0 };
+        let mut digits_start = sign_len + scan_prefix(&input[sign_len..], prefixes);
+        let mut digits_len = scan_digits(&input[digits_start..], radix, separator);
+        if digits_len == 0 && digits_start > sign_len {
+            // A prefix without digits, like the `0x` in `0xyz`, is just a `0` followed by text.
+            digits_start = sign_len;
+            digits_len = scan_digits(&input[digits_start..], radix, separator);
+        }
+        if digits_len == 0 {
+            return error_at(input, expected);
+        }
+
+        let end = digits_start + digits_len;
+        let negative = sign_len == 1 && input.first() == Some(&b'-');
+        // The digits were already checked, so the only way for this to fail is an overflow.
+        let value = input[digits_start..end]
+            .iter()
+            .filter(|c| Some(**c) != separator)
+            .try_fold(T::ZERO, |value, c| {
+                let digit = (*c as char).to_digit(radix).unwrap();
+                value.push_digit(digit, radix, negative)
+            });
+        match value {
+            Some(value) => Ok((&input[end..], value)),
+            None => error_at(input, T::RANGE_CONTEXT),
+        }
+    }
+}
+
+/// Parses a decimal integer. Signed types accept a single leading `-` or `+`,
+/// unsigned types accept only digits.
+pub fn integer<'a, T: TextInteger>() -> impl Parser<&'a [u8], T, VerboseParsingError<'a>> {
+    integer_with(10, &[], None, "integer")
+}
+
+/// Parses an integer in the given radix, without any prefix.
+///
+/// # Panics
+///
+/// If the radix isn't between 2 and 36, as digits only go up to `z`.
+pub fn integer_radix<'a, T: TextInteger>(
+    radix: u32,
+) -> impl Parser<&'a [u8], T, VerboseParsingError<'a>> {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix {} is not in 2..=36",
+        radix
+    );
+    integer_with(radix, &[], None, "integer")
+}
+
+/// Parses a hexadecimal integer with an optional `0x` prefix.
+pub fn hex_integer<'a, T: TextInteger>() -> impl Parser<&'a [u8], T, VerboseParsingError<'a>> {
+    integer_with(16, &["0x"], None, "hexadecimal integer")
+}
+
+/// Parses a binary integer with an optional `0b` prefix.
+pub fn binary_integer<'a, T: TextInteger>() -> impl Parser<&'a [u8], T, VerboseParsingError<'a>> {
+    integer_with(2, &["0b"], None, "binary integer")
+}
+
+/// Parses a decimal integer whose digits may be grouped, e.g. `1,000,000` or `1_000`.
+pub fn grouped_integer<'a, T: TextInteger>(
+    separator: u8,
+) -> impl Parser<&'a [u8], T, VerboseParsingError<'a>> {
+    integer_with(10, &[], Some(separator), "integer")
+}
+
+/// Parses a decimal float like `-1.5`, `.25` or `6.02e23`. A `.` is only part of
+/// the number when a digit follows it, so sentences ending in `4.` still parse.
+pub fn float<'a, T: FromStr>() -> impl Parser<&'a [u8], T, VerboseParsingError<'a>> {
+    move |input: &'a [u8]| {
+        let mut end = scan_sign(input);
+        let integer_len = scan_digits(&input[end..], 10, None);
+        end += integer_len;
+        let mut fraction_len = 0;
+        if input.get(end) == Some(&b'.') {
+            fraction_len = scan_digits(&input[end + 1..], 10, None);
+            if fraction_len > 0 {
+                end += 1 + fraction_len;
+            }
+        }
+        if integer_len + fraction_len == 0 {
+            return error_at(input, "float");
+        }
+        if matches!(input.get(end), Some(b'e' | b'E')) {
+            let exponent_sign = scan_sign(&input[end + 1..]);
+            let exponent_len = scan_digits(&input[end + 1 + exponent_sign..], 10, None);
+            if exponent_len > 0 {
+                end += 1 + exponent_sign + exponent_len;
+            }
+        }
+
+        // Only ASCII was scanned, so this borrows the input rather than copying it.
+        match str::from_utf8(&input[..end]).unwrap().parse::<T>() {
+            Ok(value) => Ok((&input[end..], value)),
+            Err(_) => error_at(input, "float"),
+        }
+    }
+}
+
+macro_rules! text_parser_for_int {
+    ($name:ident for $itype:ty) => {
+        pub fn $name<'a>() -> impl Parser<&'a [u8], $itype, VerboseParsingError<'a>> {
+            integer::<$itype>()
+        }
+    };
+}
+
+text_parser_for_int!(text_u8 for u8);
+text_parser_for_int!(text_u16 for u16);
+text_parser_for_int!(text_u32 for u32);
+text_parser_for_int!(text_u64 for u64);
+text_parser_for_int!(text_usize for usize);
+
+text_parser_for_int!(text_i8 for i8);
+text_parser_for_int!(text_i16 for i16);
+text_parser_for_int!(text_i32 for i32);
+text_parser_for_int!(text_i64 for i64);
+text_parser_for_int!(text_isize for isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::{parse_complete, AocParseError};
+
+    fn parse<'a, T>(
+        input: &'a [u8],
+        mut parser: impl Parser<&'a [u8], T, VerboseParsingError<'a>>,
+    ) -> Option<(&'a [u8], T)> {
+        parser.parse(input).ok()
+    }
+
+    #[test]
+    fn test_signed_grammar_is_strict() {
+        assert_eq!(parse(b"1-2", integer::<i32>()), Some((&b"-2"[..], 1)));
+        assert_eq!(parse(b"-12,", integer::<i32>()), Some((&b","[..], -12)));
+        assert_eq!(parse(b"+7", integer::<i8>()), Some((&b""[..], 7)));
+        assert_eq!(parse(b"--5", integer::<i32>()), None);
+        assert_eq!(parse(b"-", integer::<i32>()), None);
+        assert_eq!(parse(b"-5", integer::<u32>()), None);
+    }
+
+    #[test]
+    fn test_overflow_is_a_parse_error() {
+        let err: AocParseError = parse_complete(b"256", text_u8()).unwrap_err();
+        assert_eq!(
+            err.to_string().lines().next().unwrap(),
+            "expected u8 in range at line 1, column 1"
+        );
+        assert_eq!(parse(b"-128", text_i8()), Some((&b""[..], -128)));
+        assert_eq!(parse(b"-129", text_i8()), None);
+        assert_eq!(parse(b"+127", text_i8()), Some((&b""[..], 127)));
+        assert_eq!(
+            parse(b"18446744073709551615", text_u64()),
+            Some((&b""[..], u64::MAX))
+        );
+        assert_eq!(parse(b"18446744073709551616", text_u64()), None);
+        assert_eq!(parse(b"-0x80", hex_integer::<i8>()), Some((&b""[..], -128)));
+    }
+
+    #[test]
+    fn test_radix_and_groups() {
+        assert_eq!(parse(b"0x1F!", hex_integer::<u32>()), Some((&b"!"[..], 31)));
+        assert_eq!(parse(b"-ff", hex_integer::<i32>()), Some((&b""[..], -255)));
+        assert_eq!(
+            parse(b"0b1012", binary_integer::<u8>()),
+            Some((&b"2"[..], 5))
+        );
+        assert_eq!(
+            parse(b"777", integer_radix::<u16>(8)),
+            Some((&b""[..], 511))
+        );
+        assert_eq!(
+            parse(b"1,234,567, 8", grouped_integer::<u32>(b',')),
+            Some((&b", 8"[..], 1234567))
+        );
+    }
+
+    #[test]
+    fn test_prefix_without_digits() {
+        assert_eq!(parse(b"0x", hex_integer::<u32>()), Some((&b"x"[..], 0)));
+        assert_eq!(parse(b"-0xz", hex_integer::<i32>()), Some((&b"xz"[..], 0)));
+        assert_eq!(parse(b"0b2", binary_integer::<u8>()), Some((&b"b2"[..], 0)));
+        assert_eq!(parse(b"x", hex_integer::<u32>()), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "radix 37")]
+    fn test_radix_out_of_range() {
+        integer_radix::<u32>(37);
+    }
+
+    #[test]
+    fn test_float() {
+        assert_eq!(
+            parse(b"-1.5e3x", float::<f64>()),
+            Some((&b"x"[..], -1500.0))
+        );
+        assert_eq!(parse(b".25", float::<f32>()), Some((&b""[..], 0.25)));
+        assert_eq!(parse(b"4.", float::<f64>()), Some((&b"."[..], 4.0)));
+        assert_eq!(parse(b"2e", float::<f64>()), Some((&b"e"[..], 2.0)));
+        assert_eq!(parse(b".", float::<f64>()), None);
+    }
+}
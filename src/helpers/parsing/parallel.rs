@@ -0,0 +1,71 @@
+use memchr::memchr;
+use rayon::prelude::*;
+
+use super::{AocLineParsable, AocParsable, AocParseError, RecordIterator};
+
+/// Chunks smaller than this aren't worth handing to another thread.
+const MIN_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Splits the input into chunks of roughly `chunk_size` bytes that each end just
+/// after a newline, or at the end of the input.
+fn split_chunks(input: &[u8], chunk_size: usize) -> Vec<&[u8]> {
+    let mut chunks = vec![];
+    let mut start = 0;
+    while start < input.len() {
+        let target = (start + chunk_size).min(input.len());
+        let end =
+            memchr(b'\n', &input[target..]).map_or(input.len(), |newline| target + newline + 1);
+        chunks.push(&input[start..end]);
+        start = end;
+    }
+    chunks
+}
+
+/// Parses every line of the input in parallel, keeping the records in input order.
+/// If several lines fail, the error for the first one is returned, with its line
+/// and column counted from the start of the whole input.
+pub fn par_parse_all<T: AocLineParsable + Send>(input: &[u8]) -> Result<Vec<T>, AocParseError> {
+    let chunk_size = (input.len() / (rayon::current_num_threads() * 4)).max(MIN_CHUNK_SIZE);
+    let parsed_chunks: Vec<Result<Vec<T>, AocParseError>> = split_chunks(input, chunk_size)
+        .into_par_iter()
+        .map(|chunk| RecordIterator::within(input, chunk, T::parse_from_string).collect())
+        .collect();
+
+    let mut records = Vec::with_capacity(parsed_chunks.iter().flatten().map(Vec::len).sum());
+    for chunk in parsed_chunks {
+        records.extend(chunk?);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::parse_all;
+
+    #[derive(Debug, PartialEq, Eq, AocLineParsable)]
+    #[aoc(pattern = "{id}: {value}")]
+    struct Entry {
+        id: u32,
+        value: i64,
+    }
+
+    #[test]
+    fn test_par_parse_all_matches_parse_all() {
+        let input: String = (0..50_000)
+            .map(|i: i64| format!("{}: {}\n", i, i * i - 5000))
+            .collect();
+        let parallel: Vec<Entry> = par_parse_all(input.as_bytes()).unwrap();
+        assert_eq!(parallel, parse_all::<Entry>(input.as_bytes()).unwrap());
+        assert_eq!(split_chunks(b"1: 1\n2: 2\n3: 3", 3).len(), 3);
+    }
+
+    #[test]
+    fn test_par_parse_all_reports_absolute_lines() {
+        let mut lines: Vec<String> = (0..50_000).map(|i| format!("{}: {}", i, i)).collect();
+        lines[31_234] = "31234: x".to_string();
+        lines[45_000] = "45000 45000".to_string();
+        let err = par_parse_all::<Entry>(lines.join("\n").as_bytes()).unwrap_err();
+        assert_eq!((err.line(), err.column()), (31_235, 8));
+    }
+}
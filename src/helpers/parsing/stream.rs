@@ -0,0 +1,365 @@
+use std::{
+    fmt::{self, Display},
+    io::{self, BufRead},
+    marker::PhantomData,
+    ops::Range,
+};
+
+use memchr::memchr;
+
+use super::{blocks::is_blank, parse_complete, AocBlockParsable, AocLineParsable, AocParseError};
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(AocParseError),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(err) => write!(f, "failed to read input: {}", err),
+            StreamError::Parse(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+/// Hands out complete lines from whatever chunks a reader produces, keeping only
+/// the unfinished line around between chunks.
+struct LineReader<R> {
+    reader: R,
+    /// The current chunk, plus any unfinished line carried over from the previous one.
+    pending: Vec<u8>,
+    /// Where the next line starts in `pending`.
+    start: usize,
+    /// How far `pending` has already been searched for a newline.
+    scanned: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    fn new(reader: R) -> Self {
+        LineReader {
+            reader,
+            pending: vec![],
+            start: 0,
+            scanned: 0,
+        }
+    }
+
+    /// Finds the next complete line in `pending`, reading more chunks as needed.
+    /// Returns `None` at the end of the stream.
+    fn next_line(&mut self) -> io::Result<Option<Range<usize>>> {
+        loop {
+            if let Some(newline) = memchr(b'\n', &self.pending[self.scanned..]) {
+                let line = self.start..self.scanned + newline;
+                self.start = line.end + 1;
+                self.scanned = self.start;
+                return Ok(Some(line));
+            }
+
+            // Only the unfinished line has to be kept before reading the next chunk.
+            self.pending.drain(..self.start);
+            self.start = 0;
+            self.scanned = self.pending.len();
+
+            let chunk = self.reader.fill_buf()?;
+            if chunk.is_empty() {
+                if self.pending.is_empty() {
+                    return Ok(None);
+                }
+                self.start = self.pending.len();
+                self.scanned = self.start;
+                return Ok(Some(0..self.pending.len()));
+            }
+            let len = chunk.len();
+            self.pending.extend_from_slice(chunk);
+            self.reader.consume(len);
+        }
+    }
+
+    /// The contents of a line returned by `next_line`, without a trailing `\r`.
+    fn line(&self, line: Range<usize>) -> &[u8] {
+        let line = &self.pending[line];
+        line.strip_suffix(b"\r").unwrap_or(line)
+    }
+}
+
+/// Parses line records straight from a reader, so inputs never have to fit in memory.
+/// Created by `stream_records`.
+pub struct RecordStream<R, T> {
+    lines: LineReader<R>,
+    line_number: usize,
+    finished: bool,
+    record: PhantomData<T>,
+}
+
+/// Reads records one line at a time from whatever chunks `reader` hands out.
+/// Lines split across chunks are stitched back together before parsing, and
+/// errors carry the line number within the whole stream.
+///
+/// Memory use is bounded by the longest line rather than the whole input: a
+/// record has to be parsed in one piece, so a single huge line is still buffered
+/// whole. Use `find_window` for inputs that are one long signal, and
+/// `stream_blocks` for inputs where blank lines separate the records.
+pub fn stream_records<T: AocLineParsable, R: BufRead>(reader: R) -> RecordStream<R, T> {
+    RecordStream {
+        lines: LineReader::new(reader),
+        line_number: 0,
+        finished: false,
+        record: PhantomData,
+    }
+}
+
+impl<R: BufRead, T: AocLineParsable> Iterator for RecordStream<R, T> {
+    type Item = Result<T, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = match self.lines.next_line() {
+            Ok(Some(line)) => {
+                self.line_number += 1;
+                parse_complete(self.lines.line(line), T::parse_from_line)
+                    .map_err(|err| StreamError::Parse(err.with_line_offset(self.line_number - 1)))
+            }
+            Ok(None) => {
+                self.finished = true;
+                return None;
+            }
+            Err(err) => Err(StreamError::Io(err)),
+        };
+        self.finished = result.is_err();
+        Some(result)
+    }
+}
+
+/// Parses blocks of lines separated by blank lines straight from a reader.
+/// Created by `stream_blocks`.
+pub struct BlockStream<R, T> {
+    lines: LineReader<R>,
+    /// The lines of the block being read, joined by `\n`.
+    block: Vec<u8>,
+    line_number: usize,
+    finished: bool,
+    record: PhantomData<T>,
+}
+
+/// Reads one block at a time from `reader`, the streaming version of
+/// `iterate_blocks`. Only the current block is kept in memory, and blank lines
+/// around the blocks are skipped just like `iterate_blocks` does.
+pub fn stream_blocks<T: AocBlockParsable, R: BufRead>(reader: R) -> BlockStream<R, T> {
+    BlockStream {
+        lines: LineReader::new(reader),
+        block: vec![],
+        line_number: 0,
+        finished: false,
+        record: PhantomData,
+    }
+}
+
+impl<R: BufRead, T: AocBlockParsable> BlockStream<R, T> {
+    /// Collects the lines of the next block into `block`, returning the line
+    /// number it starts on, or `None` if only blank lines were left.
+    fn next_block(&mut self) -> io::Result<Option<usize>> {
+        self.block.clear();
+        let mut first_line = None;
+        while let Some(line) = self.lines.next_line()? {
+            self.line_number += 1;
+            let line = self.lines.line(line);
+            if is_blank(line) {
+                if first_line.is_some() {
+                    break;
+                }
+                continue;
+            }
+            if first_line.is_some() {
+                self.block.push(b'\n');
+            } else {
+                first_line = Some(self.line_number);
+            }
+            self.block.extend_from_slice(line);
+        }
+        Ok(first_line)
+    }
+}
+
+impl<R: BufRead, T: AocBlockParsable> Iterator for BlockStream<R, T> {
+    type Item = Result<T, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = match self.next_block() {
+            Ok(Some(first_line)) => parse_complete(&self.block, T::parse_from_block)
+                .map_err(|err| StreamError::Parse(err.with_line_offset(first_line - 1))),
+            Ok(None) => {
+                self.finished = true;
+                return None;
+            }
+            Err(err) => Err(StreamError::Io(err)),
+        };
+        self.finished = result.is_err();
+        Some(result)
+    }
+}
+
+/// Slides a window of `size` bytes along the first line of `reader` and returns
+/// how many bytes had been read once `is_match` accepts a window, or `None` if
+/// the line ends first. Only the last few windows are kept, so a signal can be
+/// searched without ever holding all of it.
+pub fn find_window<R: BufRead>(
+    mut reader: R,
+    size: usize,
+    mut is_match: impl FnMut(&[u8]) -> bool,
+) -> io::Result<Option<usize>> {
+    assert!(size > 0, "windows must hold at least one byte");
+    // Bytes are appended until there are two windows' worth, then the older one is dropped.
+    let mut window = Vec::with_capacity(2 * size);
+    let mut read = 0;
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+        let line_end = memchr(b'\n', chunk);
+        for &byte in &chunk[..line_end.unwrap_or(chunk.len())] {
+            read += 1;
+            if window.len() == 2 * size {
+                window.drain(..size);
+            }
+            window.push(byte);
+            if window.len() >= size && is_match(&window[window.len() - size..]) {
+                return Ok(Some(read));
+            }
+        }
+        if line_end.is_some() {
+            return Ok(None);
+        }
+        let len = chunk.len();
+        reader.consume(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::{text_u32, ParsingResult};
+    use nom::{
+        character::complete::{alpha1, line_ending},
+        combinator::map,
+        multi::separated_list1,
+    };
+    use std::io::BufReader;
+
+    #[derive(Debug, PartialEq, Eq, AocLineParsable)]
+    #[aoc(pattern = "{a},{b}")]
+    struct Pair {
+        a: u32,
+        b: i64,
+    }
+
+    #[test]
+    fn test_records_split_across_chunks() {
+        let input: String = (0..200)
+            .map(|i| format!("{},{}\r\n", i, -i * 1000))
+            .collect();
+        let reader = BufReader::with_capacity(3, input.as_bytes());
+        let pairs: Vec<Pair> = stream_records(reader).collect::<Result<_, _>>().unwrap();
+        assert_eq!(pairs.len(), 200);
+        assert_eq!(pairs[123], Pair { a: 123, b: -123000 });
+
+        let reader = BufReader::with_capacity(4, &b"1,2\n3,4"[..]);
+        assert_eq!(stream_records::<Pair, _>(reader).count(), 2);
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Signal(Vec<u8>);
+
+    impl AocLineParsable for Signal {
+        fn parse_from_line(input: &[u8]) -> ParsingResult<'_, Signal> {
+            map(alpha1, |signal: &[u8]| Signal(signal.to_vec()))(input)
+        }
+    }
+
+    #[test]
+    fn test_lines_longer_than_the_buffer() {
+        let long = "abcd".repeat(10_000);
+        let input = format!("{}\nxy\n{}", long, long);
+        let reader = BufReader::with_capacity(7, input.as_bytes());
+        let signals: Vec<Signal> = stream_records(reader).collect::<Result<_, _>>().unwrap();
+        let lengths: Vec<usize> = signals.iter().map(|signal| signal.0.len()).collect();
+        assert_eq!(lengths, [40_000, 2, 40_000]);
+        assert_eq!(signals[2].0, long.as_bytes());
+    }
+
+    #[test]
+    fn test_stream_errors_use_stream_lines() {
+        let reader = BufReader::with_capacity(2, &b"1,2\n3,4\n5;6\n7,8\n"[..]);
+        let mut records = stream_records::<Pair, _>(reader);
+        assert!(records.next().unwrap().is_ok());
+        assert!(records.next().unwrap().is_ok());
+        match records.next().unwrap() {
+            Err(StreamError::Parse(err)) => assert_eq!((err.line(), err.column()), (3, 2)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn test_find_window_in_a_long_signal() {
+        let all_different =
+            |window: &[u8]| (1..window.len()).all(|i| !window[..i].contains(&window[i]));
+        let signal = format!("{}cd\n", "ab".repeat(10_000));
+        let reader = BufReader::with_capacity(3, signal.as_bytes());
+        assert_eq!(find_window(reader, 4, all_different).unwrap(), Some(20_002));
+
+        let reader = BufReader::with_capacity(3, &b"mjqjpqmgbljsphdztnvjfqwrcgsmlb"[..]);
+        assert_eq!(find_window(reader, 4, all_different).unwrap(), Some(7));
+
+        let reader = BufReader::with_capacity(3, &b"aabaa\nabcd"[..]);
+        assert_eq!(find_window(reader, 4, all_different).unwrap(), None);
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Numbers(Vec<u32>);
+
+    impl AocBlockParsable for Numbers {
+        fn parse_from_block(block: &[u8]) -> ParsingResult<'_, Numbers> {
+            map(separated_list1(line_ending, text_u32()), Numbers)(block)
+        }
+    }
+
+    #[test]
+    fn test_blocks_split_across_chunks() {
+        let reader =
+            BufReader::with_capacity(2, &b"\n1000\n2000\n\n3000\r\n \r\n\n4000\n5000\n6000"[..]);
+        let blocks: Vec<Numbers> = stream_blocks(reader).collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            blocks,
+            [
+                Numbers(vec![1000, 2000]),
+                Numbers(vec![3000]),
+                Numbers(vec![4000, 5000, 6000]),
+            ]
+        );
+
+        let reader = BufReader::with_capacity(2, &b"\n\n"[..]);
+        assert_eq!(stream_blocks::<Numbers, _>(reader).count(), 0);
+    }
+
+    #[test]
+    fn test_block_errors_use_stream_lines() {
+        let reader = BufReader::with_capacity(2, &b"1\n2\n\n3\n4x\n\n5\n"[..]);
+        let mut blocks = stream_blocks::<Numbers, _>(reader);
+        assert!(blocks.next().unwrap().is_ok());
+        match blocks.next().unwrap() {
+            Err(StreamError::Parse(err)) => assert_eq!((err.line(), err.column()), (5, 2)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(blocks.next().is_none());
+    }
+}
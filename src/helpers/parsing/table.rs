@@ -0,0 +1,150 @@
+use std::ops::Range;
+
+use super::{
+    blocks::{is_blank, split_line},
+    expected_error_for_input, ParsingResult,
+};
+
+/// Which line of a table holds the column labels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LabelLine {
+    Header,
+    Footer,
+}
+
+/// A column-aligned table such as the crate stacks drawing. Cells are the trimmed
+/// text under each label, or `None` where there is only whitespace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table<'a> {
+    pub labels: Vec<&'a [u8]>,
+    pub rows: Vec<Vec<Option<&'a [u8]>>>,
+}
+
+impl<'a> Table<'a> {
+    pub fn column_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// The cells of one column, from the top row down.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = Option<&'a [u8]>> + '_ {
+        self.rows.iter().map(move |row| row[col])
+    }
+}
+
+fn trim(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|c| !c.is_ascii_whitespace())
+        .map_or(start, |last| last + 1);
+    &bytes[start..end]
+}
+
+/// Splits the label line into one span per label. Each column reaches halfway
+/// to its neighbours' labels, and the last one runs to the end of the line.
+fn column_spans(label_line: &[u8]) -> Vec<Range<usize>> {
+    let mut labels: Vec<Range<usize>> = vec![];
+    for (i, c) in label_line.iter().enumerate() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+        match labels.last_mut() {
+            Some(label) if label.end == i => label.end = i + 1,
+            _ => labels.push(i..i + 1),
+        }
+    }
+    let mut spans = Vec::with_capacity(labels.len());
+    let mut start = 0;
+    for (i, label) in labels.iter().enumerate() {
+        let end = match labels.get(i + 1) {
+            Some(next) => (label.end + next.start) / 2,
+            None => usize::MAX,
+        };
+        spans.push(start..end);
+        start = end;
+    }
+    spans
+}
+
+fn cells<'a>(line: &'a [u8], spans: &[Range<usize>]) -> Vec<Option<&'a [u8]>> {
+    spans
+        .iter()
+        .map(|span| {
+            let start = span.start.min(line.len());
+            let end = span.end.min(line.len());
+            Some(trim(&line[start..end])).filter(|cell| !cell.is_empty())
+        })
+        .collect()
+}
+
+/// Parses a fixed-width table up to the next blank line, taking the column
+/// boundaries from the label line. Lines may be shorter than the label line,
+/// so trimmed trailing whitespace is fine.
+pub fn parse_table(input: &[u8], label_line: LabelLine) -> ParsingResult<'_, Table<'_>> {
+    let mut lines = vec![];
+    let mut rest = input;
+    while !rest.is_empty() {
+        let (line, after_line) = split_line(rest);
+        if is_blank(line) {
+            break;
+        }
+        lines.push(line);
+        rest = after_line;
+    }
+
+    let labels = match label_line {
+        LabelLine::Header => lines.first(),
+        LabelLine::Footer => lines.last(),
+    };
+    let labels = match labels {
+        Some(labels) => *labels,
+        None => return expected_error_for_input(input, "table"),
+    };
+    let spans = column_spans(labels);
+    let rows = match label_line {
+        LabelLine::Header => &lines[1..],
+        LabelLine::Footer => &lines[..lines.len() - 1],
+    };
+    Ok((
+        rest,
+        Table {
+            labels: cells(labels, &spans).into_iter().flatten().collect(),
+            rows: rows.iter().map(|line| cells(line, &spans)).collect(),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_footer_labels_beyond_nine() {
+        let input = b"[A]                                     [K]\n\
+                      [B] [C] [D] [E] [F] [G] [H] [I] [J] [L] [M]\n \
+                      1   2   3   4   5   6   7   8   9   10  11\n\nmove";
+        let (rest, table) = parse_table(input, LabelLine::Footer).unwrap();
+        assert_eq!(rest, b"\nmove");
+        assert_eq!(table.column_count(), 11);
+        assert_eq!(table.labels[9], b"10");
+        assert_eq!(table.rows[0][0], Some(&b"[A]"[..]));
+        assert_eq!(table.rows[0][1], None);
+        assert_eq!(table.rows[0][10], Some(&b"[K]"[..]));
+        assert_eq!(
+            table.column(9).collect::<Vec<_>>(),
+            vec![None, Some(&b"[L]"[..])]
+        );
+    }
+
+    #[test]
+    fn test_header_labels_with_ragged_rows() {
+        let input = b"name  age\r\nbob   42\r\nalice\r\n";
+        let (_, table) = parse_table(input, LabelLine::Header).unwrap();
+        assert_eq!(table.labels, vec![&b"name"[..], &b"age"[..]]);
+        assert_eq!(table.rows[0], vec![Some(&b"bob"[..]), Some(&b"42"[..])]);
+        assert_eq!(table.rows[1], vec![Some(&b"alice"[..]), None]);
+    }
+}
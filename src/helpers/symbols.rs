@@ -0,0 +1,132 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use nom::Parser;
+
+use super::parsing::{ParsingResult, VerboseParsingError};
+
+/// A compact id for an interned name. Symbols are `Copy`, `Ord` and `Hash`, so
+/// they can be used directly as `GraphMap` nodes or `HashMap` keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(pub u32);
+
+impl Symbol {
+    /// Symbols are handed out in order from zero, so this can index a `Vec`.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Maps names to `Symbol`s and back.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolTable {
+    symbols: HashMap<Box<str>, Symbol>,
+    names: Vec<Box<str>>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the symbol for a name, adding it if it hasn't been seen before.
+    pub fn intern(&mut self, name: impl AsRef<[u8]>) -> Symbol {
+        let name = String::from_utf8_lossy(name.as_ref());
+        if let Some(symbol) = self.symbols.get(name.as_ref()) {
+            return *symbol;
+        }
+        let symbol = Symbol(u32::try_from(self.names.len()).expect("Too many symbols"));
+        let name: Box<str> = name.into();
+        self.names.push(name.clone());
+        self.symbols.insert(name, symbol);
+        symbol
+    }
+
+    /// Looks up a name without adding it.
+    pub fn get(&self, name: impl AsRef<[u8]>) -> Option<Symbol> {
+        let name = String::from_utf8_lossy(name.as_ref());
+        self.symbols.get(name.as_ref()).copied()
+    }
+
+    /// Panics if the symbol came from a different table.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.names[symbol.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> + '_ {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (Symbol(i as u32), name.as_ref()))
+    }
+}
+
+/// Interns whatever `name` matches. The table sits in a `RefCell` so that several
+/// of these parsers, such as both ends of an edge, can share it.
+pub fn symbol<'a, 't>(
+    table: &'t RefCell<SymbolTable>,
+    mut name: impl Parser<&'a [u8], &'a [u8], VerboseParsingError<'a>> + 't,
+) -> impl FnMut(&'a [u8]) -> ParsingResult<'a, Symbol> + 't {
+    move |input| {
+        let (rest, name) = name.parse(input)?;
+        Ok((rest, table.borrow_mut().intern(name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::parse_complete;
+    use nom::{
+        bytes::complete::tag, character::complete::alpha1, multi::separated_list1,
+        sequence::separated_pair,
+    };
+    use petgraph::graphmap::DiGraphMap;
+
+    #[test]
+    fn test_interning() {
+        let mut table = SymbolTable::new();
+        let aa = table.intern("AA");
+        let bb = table.intern(b"BB");
+        assert_eq!(table.intern("AA"), aa);
+        assert_ne!(aa, bb);
+        assert_eq!(table.resolve(bb), "BB");
+        assert_eq!(table.get("CC"), None);
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn test_symbol_parser_builds_graph() {
+        let table = RefCell::new(SymbolTable::new());
+        let (from, to) = parse_complete(
+            b"AA -> BB, CC, AA",
+            separated_pair(
+                symbol(&table, alpha1),
+                tag(" -> "),
+                separated_list1(tag(", "), symbol(&table, alpha1)),
+            ),
+        )
+        .unwrap();
+
+        let graph: DiGraphMap<Symbol, ()> = to.iter().map(|to| (from, *to)).collect();
+        let table = table.into_inner();
+        assert_eq!(table.len(), 3);
+        assert_eq!(graph.node_count(), 3);
+        let cc = table.get("CC").unwrap();
+        assert!(graph.contains_edge(from, cc));
+        assert_eq!(
+            graph
+                .neighbors(from)
+                .map(|node| table.resolve(node))
+                .collect::<Vec<_>>(),
+            vec!["BB", "CC", "AA"]
+        );
+    }
+}
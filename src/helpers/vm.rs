@@ -0,0 +1,267 @@
+use std::fmt::{self, Debug};
+
+use nom::{branch::alt, bytes::complete::tag, combinator::map, sequence::preceded};
+
+use super::{
+    grids::Grid,
+    parsing::{text_i64, AocLineParsable, ParsingResult},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(i64),
+}
+
+impl AocLineParsable for Instruction {
+    fn parse_from_line(input: &[u8]) -> ParsingResult<'_, Instruction> {
+        alt((
+            map(tag("noop"), |_| Instruction::Noop),
+            map(preceded(tag("addx "), text_i64()), Instruction::Addx),
+        ))(input)
+    }
+}
+
+/// How many cycles each instruction takes to complete.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CycleCosts {
+    pub noop: usize,
+    pub addx: usize,
+}
+
+impl Default for CycleCosts {
+    fn default() -> Self {
+        CycleCosts { noop: 1, addx: 2 }
+    }
+}
+
+impl CycleCosts {
+    pub fn cost(&self, instruction: &Instruction) -> usize {
+        match instruction {
+            Instruction::Noop => self.noop,
+            Instruction::Addx(_) => self.addx,
+        }
+    }
+}
+
+/// The registers as seen *during* a cycle, before the instruction finishing on
+/// that cycle has taken effect. Cycles are numbered from 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CycleState<const R: usize = 1> {
+    pub cycle: usize,
+    pub registers: [i64; R],
+}
+
+impl<const R: usize> CycleState<R> {
+    /// The first register, which `addx` adds to.
+    pub fn x(&self) -> i64 {
+        self.registers[0]
+    }
+
+    pub fn signal_strength(&self) -> i64 {
+        self.cycle as i64 * self.x()
+    }
+}
+
+/// Runs programs one cycle at a time, calling every hook on each cycle.
+///
+/// The machine has `R` registers, the first of which is `x`. To support a new
+/// op, add a variant to `Instruction` along with its parser, give it a field in
+/// `CycleCosts`, and apply its effect on the registers in `Machine::apply`.
+pub struct Machine<'h, const R: usize = 1> {
+    pub registers: [i64; R],
+    pub cycle: usize,
+    costs: CycleCosts,
+    hooks: Vec<Box<dyn FnMut(CycleState<R>) + 'h>>,
+}
+
+impl<'h> Default for Machine<'h> {
+    fn default() -> Self {
+        Self::with_costs(CycleCosts::default())
+    }
+}
+
+impl<'h> Machine<'h> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A machine with just the `x` register, starting at 1.
+    pub fn with_costs(costs: CycleCosts) -> Self {
+        Self::with_registers(costs, [1])
+    }
+}
+
+impl<'h, const R: usize> Machine<'h, R> {
+    pub fn with_registers(costs: CycleCosts, registers: [i64; R]) -> Self {
+        assert!(R > 0, "a machine needs at least the x register");
+        Machine {
+            registers,
+            cycle: 0,
+            costs,
+            hooks: vec![],
+        }
+    }
+
+    pub fn x(&self) -> i64 {
+        self.registers[0]
+    }
+
+    pub fn on_cycle(&mut self, hook: impl FnMut(CycleState<R>) + 'h) -> &mut Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    pub fn execute(&mut self, instruction: &Instruction) {
+        for _ in 0..self.costs.cost(instruction) {
+            self.cycle += 1;
+            let state = CycleState {
+                cycle: self.cycle,
+                registers: self.registers,
+            };
+            for hook in &mut self.hooks {
+                hook(state);
+            }
+        }
+        self.apply(instruction);
+    }
+
+    /// Changes the registers once an instruction completes.
+    fn apply(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::Noop => {}
+            Instruction::Addx(value) => self.registers[0] += value,
+        }
+    }
+
+    pub fn run<'p>(&mut self, program: impl IntoIterator<Item = &'p Instruction>) {
+        for instruction in program {
+            self.execute(instruction);
+        }
+    }
+}
+
+/// A screen drawn one pixel per cycle, left to right and top to bottom. A pixel
+/// is lit when the three pixel wide sprite centred on `x` covers its column.
+pub struct Crt {
+    pixels: Grid<bool>,
+}
+
+impl Crt {
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width > 0 && height > 0, "a CRT needs at least one pixel");
+        Crt {
+            pixels: Grid::new_with(width, height, false),
+        }
+    }
+
+    /// Draws a whole program on a fresh screen.
+    pub fn render<'p>(
+        width: usize,
+        height: usize,
+        costs: CycleCosts,
+        program: impl IntoIterator<Item = &'p Instruction>,
+    ) -> Self {
+        let mut crt = Crt::new(width, height);
+        let mut machine = Machine::with_costs(costs);
+        machine.on_cycle(|state| crt.draw(state));
+        machine.run(program);
+        drop(machine);
+        crt
+    }
+
+    /// Cycles past the last pixel wrap back around to the top. Cycle 0 comes
+    /// before the first pixel, so nothing is drawn for it.
+    pub fn draw<const R: usize>(&mut self, state: CycleState<R>) {
+        let Some(position) = state.cycle.checked_sub(1) else {
+            return;
+        };
+        let width = self.pixels.width();
+        let position = position % (width * self.pixels.height());
+        let (row, col) = (position / width, position % width);
+        self.pixels[row][col] = (state.x() - col as i64).abs() <= 1;
+    }
+
+    pub fn pixels(&self) -> &Grid<bool> {
+        &self.pixels
+    }
+
+    pub fn into_grid(self) -> Grid<bool> {
+        self.pixels
+    }
+}
+
+impl Debug for Crt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.pixels, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parsing::parse_all;
+
+    fn program(text: &str) -> Vec<Instruction> {
+        parse_all(text.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_cycle_hooks() {
+        let program = program("noop\naddx 3\naddx -5\n");
+        let mut seen = vec![];
+        let mut strengths = 0;
+        let mut machine = Machine::new();
+        machine
+            .on_cycle(|state| seen.push(state.x()))
+            .on_cycle(|state| strengths += state.signal_strength());
+        machine.run(&program);
+        assert_eq!((machine.cycle, machine.x()), (5, -1));
+        drop(machine);
+        assert_eq!(seen, [1, 1, 1, 4, 4]);
+        assert_eq!(strengths, 1 + 2 + 3 + 16 + 20);
+    }
+
+    #[test]
+    fn test_custom_costs() {
+        let program = program("noop\naddx 3\naddx -5");
+        let mut machine = Machine::with_costs(CycleCosts { noop: 2, addx: 3 });
+        machine.run(&program);
+        assert_eq!((machine.cycle, machine.x()), (8, -1));
+    }
+
+    #[test]
+    fn test_crt_rendering() {
+        let program = program("addx 3\naddx 3\nnoop\naddx -6\naddx 2\nnoop\nnoop\nnoop");
+        let crt = Crt::render(4, 3, CycleCosts::default(), &program);
+        assert_eq!(format!("{:?}", crt), "##.#\n....\n#.##\n");
+    }
+
+    #[test]
+    fn test_extra_registers() {
+        let program = program("addx 3\nnoop");
+        let mut states = vec![];
+        let mut machine = Machine::with_registers(CycleCosts::default(), [1, 7]);
+        machine.on_cycle(|state| states.push(state.registers));
+        machine.run(&program);
+        assert_eq!(machine.registers, [4, 7]);
+        drop(machine);
+        assert_eq!(states, [[1, 7], [1, 7], [4, 7]]);
+    }
+
+    #[test]
+    fn test_crt_ignores_cycle_zero() {
+        let mut crt = Crt::new(2, 1);
+        crt.draw(CycleState {
+            cycle: 0,
+            registers: [0],
+        });
+        assert_eq!(format!("{:?}", crt), "..\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one pixel")]
+    fn test_crt_without_pixels() {
+        Crt::new(0, 6);
+    }
+}
@@ -6,6 +6,9 @@
 use std::env;
 use std::fs;
 
+// Lets the derive macros refer to `::advent_of_code` from inside this crate too.
+extern crate self as advent_of_code;
+
 pub mod helpers;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -41,13 +44,86 @@ macro_rules! solve {
     }};
 }
 
+/// What to do with newlines at the very end of an input file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrailingNewline {
+    Keep,
+    /// End with exactly one newline, so the last line looks like every other line.
+    Ensure,
+    Strip,
+}
+
+/// How `read_file` cleans up input files before handing them to a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InputFormat {
+    pub strip_bom: bool,
+    pub normalize_line_endings: bool,
+    pub strip_trailing_whitespace: bool,
+    pub trailing_newline: TrailingNewline,
+}
+
+impl InputFormat {
+    /// Leaves the file exactly as it is, for puzzles where whitespace matters.
+    pub const RAW: InputFormat = InputFormat {
+        strip_bom: false,
+        normalize_line_endings: false,
+        strip_trailing_whitespace: false,
+        trailing_newline: TrailingNewline::Keep,
+    };
+}
+
+impl Default for InputFormat {
+    fn default() -> Self {
+        InputFormat {
+            strip_bom: true,
+            normalize_line_endings: true,
+            strip_trailing_whitespace: false,
+            trailing_newline: TrailingNewline::Ensure,
+        }
+    }
+}
+
+pub fn normalize_input(text: &str, format: InputFormat) -> String {
+    let mut text = text;
+    if format.strip_bom {
+        text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    }
+    let mut normalized = if format.normalize_line_endings {
+        text.replace("\r\n", "\n")
+    } else {
+        text.to_string()
+    };
+    if format.strip_trailing_whitespace {
+        normalized = normalized
+            .split('\n')
+            .map(|line| line.trim_end_matches(|c: char| c.is_whitespace() && c != '\r'))
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+    match format.trailing_newline {
+        TrailingNewline::Keep => {}
+        TrailingNewline::Strip => normalized.truncate(normalized.trim_end_matches('\n').len()),
+        TrailingNewline::Ensure if normalized.is_empty() => {}
+        TrailingNewline::Ensure => {
+            normalized.truncate(normalized.trim_end_matches('\n').len());
+            normalized.push('\n');
+        }
+    }
+    normalized
+}
+
+/// Reads an input or example file, normalized with the default `InputFormat`.
 pub fn read_file(folder: &str, day: u8) -> String {
+    read_file_with(folder, day, InputFormat::default())
+}
+
+pub fn read_file_with(folder: &str, day: u8, format: InputFormat) -> String {
     let cwd = env::current_dir().unwrap();
 
     let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
 
     let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    normalize_input(&f.expect("could not open input file"), format)
 }
 
 fn parse_time(val: &str, postfix: &str) -> f64 {
@@ -122,4 +198,20 @@ mod tests {
             10400.50_f64
         );
     }
+
+    #[test]
+    fn test_normalize_input() {
+        let text = "\u{feff}a \r\nb\r\n\r\n\r\n";
+        assert_eq!(normalize_input(text, InputFormat::default()), "a \nb\n");
+        assert_eq!(normalize_input("a\nb", InputFormat::default()), "a\nb\n");
+        assert_eq!(normalize_input("", InputFormat::default()), "");
+        assert_eq!(normalize_input(text, InputFormat::RAW), text);
+
+        let format = InputFormat {
+            strip_trailing_whitespace: true,
+            trailing_newline: TrailingNewline::Strip,
+            ..InputFormat::default()
+        };
+        assert_eq!(normalize_input(" a \t\n b \n\n", format), " a\n b");
+    }
 }
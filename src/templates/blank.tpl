@@ -0,0 +1,32 @@
+pub fn part_one(input: &str) -> Option<{{ANSWER_ONE}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{ANSWER_TWO}}> {
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{DAY}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        let expected: Option<{{ANSWER_ONE}}> = None;
+        assert_eq!(part_one(&input), expected);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        let expected: Option<{{ANSWER_TWO}}> = None;
+        assert_eq!(part_two(&input), expected);
+    }
+}
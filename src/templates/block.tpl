@@ -0,0 +1,50 @@
+use advent_of_code::helpers::parsing::{
+    parse_all_blocks, text_u32, AocBlockParsable, ParsingResult,
+};
+use nom::{character::complete::line_ending, combinator::map, multi::separated_list1};
+
+#[derive(Debug)]
+struct Block {
+    lines: Vec<u32>,
+}
+
+impl AocBlockParsable for Block {
+    fn parse_from_block(input: &[u8]) -> ParsingResult<'_, Block> {
+        map(separated_list1(line_ending, text_u32()), |lines| Block { lines })(input)
+    }
+}
+
+pub fn part_one(input: &str) -> Option<{{ANSWER_ONE}}> {
+    let _blocks: Vec<Block> = parse_all_blocks(input.as_bytes()).unwrap();
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{ANSWER_TWO}}> {
+    let _blocks: Vec<Block> = parse_all_blocks(input.as_bytes()).unwrap();
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{DAY}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        let expected: Option<{{ANSWER_ONE}}> = None;
+        assert_eq!(part_one(&input), expected);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        let expected: Option<{{ANSWER_TWO}}> = None;
+        assert_eq!(part_two(&input), expected);
+    }
+}
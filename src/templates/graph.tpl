@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+
+use advent_of_code::helpers::{
+    parsing::{line_ending_or_eof, parse_complete},
+    symbols::{symbol, Symbol, SymbolTable},
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::alphanumeric1,
+    multi::many0,
+    sequence::{separated_pair, terminated},
+};
+use petgraph::graphmap::UnGraphMap;
+
+fn build_graph(input: &[u8]) -> (UnGraphMap<Symbol, ()>, SymbolTable) {
+    let symbols = RefCell::new(SymbolTable::new());
+    let edges = parse_complete(
+        input,
+        many0(terminated(
+            separated_pair(
+                symbol(&symbols, alphanumeric1),
+                tag("-"),
+                symbol(&symbols, alphanumeric1),
+            ),
+            line_ending_or_eof(),
+        )),
+    )
+    .unwrap();
+    (UnGraphMap::from_edges(edges), symbols.into_inner())
+}
+
+pub fn part_one(input: &str) -> Option<{{ANSWER_ONE}}> {
+    let (_graph, _symbols) = build_graph(input.as_bytes());
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{ANSWER_TWO}}> {
+    let (_graph, _symbols) = build_graph(input.as_bytes());
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{DAY}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        let expected: Option<{{ANSWER_ONE}}> = None;
+        assert_eq!(part_one(&input), expected);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        let expected: Option<{{ANSWER_TWO}}> = None;
+        assert_eq!(part_two(&input), expected);
+    }
+}
@@ -0,0 +1,52 @@
+use advent_of_code::helpers::parsing::{iterate_all, text_u32, AocLineParsable, ParsingResult};
+use nom::{bytes::complete::tag, combinator::map, sequence::tuple};
+
+#[derive(Debug)]
+struct Record {
+    value: u32,
+}
+
+impl AocLineParsable for Record {
+    fn parse_from_line(input: &[u8]) -> ParsingResult<'_, Record> {
+        map(tuple((tag("value "), text_u32())), |(_, value)| Record { value })(input)
+    }
+}
+
+pub fn part_one(input: &str) -> Option<{{ANSWER_ONE}}> {
+    let _records: Vec<Record> = iterate_all(input.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{ANSWER_TWO}}> {
+    let _records: Vec<Record> = iterate_all(input.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{DAY}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        let expected: Option<{{ANSWER_ONE}}> = None;
+        assert_eq!(part_one(&input), expected);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        let expected: Option<{{ANSWER_TWO}}> = None;
+        assert_eq!(part_two(&input), expected);
+    }
+}